@@ -9,4 +9,5 @@ pub mod cell_select;
 pub mod cleanup;
 pub mod stage_clear;
 pub mod setup_cells;
-pub mod play_answer;
\ No newline at end of file
+pub mod play_answer;
+pub mod campaign;
\ No newline at end of file
@@ -0,0 +1,259 @@
+use bevy::asset::{AssetServer, Assets, LoadState};
+use bevy::hierarchy::{BuildChildren, ChildBuilder, DespawnRecursiveExt};
+use bevy::input::ButtonInput;
+use bevy::prelude::{BackgroundColor, ButtonBundle, Changed, Color, Commands, Component, default, Display, Entity, In, Interaction, JustifyText, KeyCode, NodeBundle, Query, Res, ResMut, TextBundle, With};
+use bevy::text::{Text, TextStyle};
+use bevy::time::Stopwatch;
+use bevy::ui::{AlignItems, FlexDirection, JustifyContent, RepeatedGridTrack, Style, Val};
+use bevy_flurx::prelude::*;
+
+use crate::action::setup_cells::reset_stage;
+use crate::consts::CELL_COLOR;
+use crate::plugin::campaign::{Campaign, CampaignLevel, CampaignProgress, PackAsset};
+use crate::plugin::secret::format_time;
+use crate::plugin::stage::{Answer, AnswerSteps, PuzzleStage, StageRatios, StageSeed};
+use crate::plugin::stage_clear::PlayAnswerMode;
+
+const LEVELS_PER_ROW: u16 = 10;
+
+const LEVEL_BUTTON_SIZE: f32 = 96.;
+
+/// The overlay listing the levels of the campaign.
+#[derive(Component)]
+struct LevelSelect;
+
+#[derive(Component, Copy, Clone)]
+struct LevelButton(usize);
+
+/// Shows the level-select grid and waits until an unlocked level is clicked.
+///
+/// Outputs `None` if the grid is closed with Escape instead.
+pub fn select_level() -> ActionSeed<(), Option<usize>> {
+    once::run(spawn_level_select)
+        .then(wait::output(read_level_select))
+        .through(once::run(despawn_level_select))
+}
+
+/// Puts the level at the input index on the board and starts its stopwatch.
+pub fn start_level() -> ActionSeed<usize> {
+    once::run(load_level)
+        .then(reset_stage())
+}
+
+/// Outputs the level to play after a stage clear.
+///
+/// That is the next level if the current one has been cleared, or the same level if its answer was only played.
+/// Outputs `None` if no campaign level is being played, or the last one has been cleared,
+/// in which case the campaign is left.
+pub fn next_level() -> ActionSeed<(), Option<usize>> {
+    once::run(|
+        mut commands: Commands,
+        level: Option<Res<CampaignLevel>>,
+        progress: Res<CampaignProgress>,
+        campaign: Res<Campaign>,
+        packs: Res<Assets<PackAsset>>,
+    | {
+        let level = level?.index;
+        let next = if progress.record(level).cleared { level + 1 } else { level };
+        if packs.get(&campaign.pack).is_some_and(|pack| next < pack.stages.len()) {
+            Some(next)
+        } else {
+            commands.remove_resource::<CampaignLevel>();
+            None
+        }
+    })
+}
+
+/// Goes back to randomly generated stages.
+pub fn leave_campaign() -> ActionSeed {
+    once::run(|mut commands: Commands| {
+        commands.remove_resource::<CampaignLevel>();
+    })
+}
+
+/// Records the time and the number of moves of the campaign level just cleared,
+/// unless its answer was played instead of the player.
+pub fn record_level_clear(
+    mut progress: ResMut<CampaignProgress>,
+    level: Option<Res<CampaignLevel>>,
+    stage: Res<PuzzleStage>,
+    play_answer_mode: Option<Res<PlayAnswerMode>>,
+) {
+    let Some(level) = level else {
+        return;
+    };
+    if play_answer_mode.is_none() {
+        progress.record_clear(level.index, level.stopwatch.elapsed(), stage.position());
+    }
+}
+
+fn load_level(
+    In(index): In<usize>,
+    mut commands: Commands,
+    campaign: Res<Campaign>,
+    packs: Res<Assets<PackAsset>>,
+) {
+    let Some(notation) = packs.get(&campaign.pack).and_then(|pack| pack.stages.get(index)) else {
+        return;
+    };
+    let ratios = notation.cells.as_slice().try_into().expect("the pack loader checks the number of cells");
+    commands.insert_resource(StageSeed(notation.seed.unwrap_or_default()));
+    commands.insert_resource(StageRatios(ratios));
+    commands.insert_resource(Answer(notation.goal));
+    commands.insert_resource(AnswerSteps(notation.steps.clone()));
+    commands.insert_resource(CampaignLevel {
+        index,
+        stopwatch: Stopwatch::new(),
+    });
+}
+
+fn read_level_select(
+    buttons: Query<(&Interaction, &LevelButton), Changed<Interaction>>,
+    progress: Res<CampaignProgress>,
+    keys: Res<ButtonInput<KeyCode>>,
+) -> Option<Option<usize>> {
+    if keys.just_pressed(KeyCode::Escape) {
+        return Some(None);
+    }
+    buttons.iter().find_map(|(interaction, LevelButton(level))| {
+        (*interaction == Interaction::Pressed && progress.is_unlocked(*level)).then_some(Some(*level))
+    })
+}
+
+fn spawn_level_select(
+    mut commands: Commands,
+    campaign: Res<Campaign>,
+    packs: Res<Assets<PackAsset>>,
+    asset_server: Res<AssetServer>,
+    progress: Res<CampaignProgress>,
+) {
+    commands.spawn((
+        LevelSelect,
+        NodeBundle {
+            style: Style {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(32.),
+                ..default()
+            },
+            background_color: BackgroundColor(Color::BLACK.with_a(0.8)),
+            ..default()
+        }
+    ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                text: Text::from_section("Campaign", TextStyle {
+                    font_size: 80.,
+                    color: Color::GOLD,
+                    ..default()
+                }),
+                ..default()
+            });
+            match packs.get(&campaign.pack) {
+                Some(pack) => spawn_level_grid(parent, pack, &progress),
+                None => {
+                    let message = if matches!(asset_server.load_state(campaign.pack.id()), LoadState::Failed) {
+                        "The campaign failed to load"
+                    } else {
+                        "Loading the campaign..."
+                    };
+                    parent.spawn(TextBundle {
+                        text: Text::from_section(message, message_style()),
+                        ..default()
+                    });
+                }
+            }
+            parent.spawn(TextBundle {
+                text: Text::from_section("[Esc]: Back", message_style()),
+                ..default()
+            });
+        });
+}
+
+/// Spawns a button for every level: cleared ones in gold with their best records,
+/// unlocked ones in the cell color, and locked ones in gray.
+fn spawn_level_grid(
+    parent: &mut ChildBuilder,
+    pack: &PackAsset,
+    progress: &CampaignProgress,
+) {
+    parent.spawn(NodeBundle {
+        style: Style {
+            display: Display::Grid,
+            grid_template_columns: RepeatedGridTrack::px(LEVELS_PER_ROW, LEVEL_BUTTON_SIZE),
+            row_gap: Val::Px(12.),
+            column_gap: Val::Px(12.),
+            ..default()
+        },
+        ..default()
+    })
+        .with_children(|grid| {
+            for level in 0..pack.stages.len() {
+                let record = progress.record(level);
+                let color = if record.cleared {
+                    Color::GOLD
+                } else if progress.is_unlocked(level) {
+                    CELL_COLOR
+                } else {
+                    Color::DARK_GRAY
+                };
+                grid.spawn((
+                    LevelButton(level),
+                    ButtonBundle {
+                        style: Style {
+                            width: Val::Px(LEVEL_BUTTON_SIZE),
+                            height: Val::Px(LEVEL_BUTTON_SIZE),
+                            display: Display::Flex,
+                            flex_direction: FlexDirection::Column,
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        background_color: BackgroundColor(color),
+                        ..default()
+                    }
+                ))
+                    .with_children(|button| {
+                        button.spawn(TextBundle {
+                            text: Text::from_section(format!("{}", level + 1), TextStyle {
+                                font_size: 40.,
+                                color: Color::BLACK,
+                                ..default()
+                            }),
+                            ..default()
+                        });
+                        if let (Some(time), Some(moves)) = (record.best_time, record.best_moves) {
+                            button.spawn(TextBundle {
+                                text: Text::from_section(format!("{}\n{moves} moves", format_time(time.as_secs_f64())), TextStyle {
+                                    font_size: 16.,
+                                    color: Color::BLACK,
+                                    ..default()
+                                }).with_justify(JustifyText::Center),
+                                ..default()
+                            });
+                        }
+                    });
+            }
+        });
+}
+
+fn despawn_level_select(
+    mut commands: Commands,
+    overlays: Query<Entity, With<LevelSelect>>,
+) {
+    for entity in overlays.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn message_style() -> TextStyle {
+    TextStyle {
+        font_size: 48.,
+        color: Color::GOLD,
+        ..default()
+    }
+}
@@ -1,9 +1,9 @@
-use std::f32::consts::PI;
 use std::time::Duration;
 
 use bevy::asset::AssetServer;
+use bevy::hierarchy::BuildChildren;
 use bevy::math::{Quat, Vec2, Vec3};
-use bevy::prelude::{Color, Commands, default, EventWriter, In, IntoSystem, Query, Res, Sprite, SpriteBundle, System, Transform};
+use bevy::prelude::{Color, Commands, default, EventWriter, In, Query, Res, Sprite, SpriteBundle, Text, Text2dBundle, TextStyle, Transform};
 use bevy_flurx::action::{Action, delay};
 use bevy_flurx::prelude::*;
 use bevy_mod_picking::events::{Down, Pointer};
@@ -12,7 +12,7 @@ use bevy_mod_picking::prelude::{ListenerInput, On};
 use bevy_tweening::{Animator, EaseMethod, Tween, TweenCompleted};
 use bevy_tweening::lens::TransformPositionLens;
 
-use puzzle_core::move_dir::MoveDir;
+use puzzle_core::topology::Topology;
 
 use crate::arrow::{Arrow, ArrowSelected};
 use crate::consts::PUZZLE_HALF;
@@ -22,35 +22,44 @@ pub fn select_cell() -> Action<Duration> {
     delay::time().with(Duration::from_millis(100))
         .then(once::event::clear::<CellSelected>())
         .then(wait::event::read::<CellSelected>())
-        .pipe(once::run(spawn_arrow(MoveDir::Right)))
-        .pipe(once::run(spawn_arrow(MoveDir::RightUp)))
-        .pipe(once::run(spawn_arrow(MoveDir::Up)))
-        .pipe(once::run(spawn_arrow(MoveDir::LeftUp)))
-        .pipe(once::run(spawn_arrow(MoveDir::Left)))
-        .pipe(once::run(spawn_arrow(MoveDir::LeftDown)))
-        .pipe(once::run(spawn_arrow(MoveDir::Down)))
-        .pipe(once::run(spawn_arrow(MoveDir::RightDown)))
+        .pipe(once::run(spawn_arrows))
         .pipe(once::run(|In(CellSelected(cell, _)): In<CellSelected>, mut commands: Commands| {
             commands.entity(cell).insert(MoveSource);
         }))
         .then(wait::event::comes::<TweenCompleted>())
 }
 
-fn spawn_arrow(dir: MoveDir) -> impl System<In=CellSelected, Out=CellSelected> {
-    IntoSystem::into_system(move |In(event): In<CellSelected>,
-                                  mut commands: Commands,
-                                  stage: Res<PuzzleStage>,
-                                  puzzle: Query<&Transform>,
-                                  asset: Res<AssetServer>,
-    | {
-        if !stage.0.can_move(event.1, dir) {
-            return event;
-        }
-        let start = puzzle.get(event.0).unwrap().translation;
-        #[cfg(not(debug_assertions))]
-        const ASSET_PATH: &str = "arrow_release.png";
-        #[cfg(debug_assertions)]
-        const ASSET_PATH: &str = "arrow.png";
+/// Spawns an arrow toward every cell the selected cell can move to.
+///
+/// The directions come from the board's topology,
+/// each arrow points at the position of the destination cell,
+/// and is labelled with the operation the stage's rule set performs in that direction.
+fn spawn_arrows(
+    In(event): In<CellSelected>,
+    mut commands: Commands,
+    stage: Res<PuzzleStage>,
+    puzzle: Query<&Transform>,
+    asset: Res<AssetServer>,
+) -> CellSelected {
+    #[cfg(not(debug_assertions))]
+    const ASSET_PATH: &str = "arrow_release.png";
+    #[cfg(debug_assertions)]
+    const ASSET_PATH: &str = "arrow.png";
+    const ARROW_DISTANCE: f32 = 0.85;
+    const ARROW_LABEL_FONT_SIZE: f32 = 24.;
+
+    let start = puzzle.get(event.0).unwrap().translation;
+    let from = stage.topology().position(event.1);
+    for dir in stage.topology().directions().iter().copied() {
+        let Some(operation) = stage.operation(event.1, dir) else {
+            continue;
+        };
+        let Some(dist_no) = stage.dist_no(event.1, dir) else {
+            continue;
+        };
+        let to = stage.topology().position(dist_no);
+        let dir_vec = Vec2::new(to.x - from.x, to.y - from.y).normalize();
+        let angle = dir_vec.y.atan2(dir_vec.x);
 
         commands.spawn((
             SpriteBundle {
@@ -60,7 +69,7 @@ fn spawn_arrow(dir: MoveDir) -> impl System<In=CellSelected, Out=CellSelected> {
                     ..default()
                 },
                 texture: asset.load(ASSET_PATH),
-                transform: Transform::from_rotation(to_quat(&dir)),
+                transform: Transform::from_rotation(Quat::from_rotation_z(angle)),
                 ..default()
             },
             Animator::new(Tween::new(
@@ -68,28 +77,28 @@ fn spawn_arrow(dir: MoveDir) -> impl System<In=CellSelected, Out=CellSelected> {
                 Duration::from_millis(200),
                 TransformPositionLens {
                     start,
-                    end: to_vec3(dir) * PUZZLE_HALF + Vec3::new(0., 0., 10.) + start,
+                    end: (dir_vec * ARROW_DISTANCE * PUZZLE_HALF).extend(10.) + start,
                 },
             ).with_completed_event(0)),
             PickableBundle::default(),
             On::<Pointer<Down>>::run(send_arrow_selected),
             Arrow(dir)
-        ));
-        event
-    })
-}
-
-fn to_quat(dir: &MoveDir) -> Quat {
-    match dir {
-        MoveDir::Right => Quat::default(),
-        MoveDir::RightUp => Quat::from_rotation_z(PI / 4.),
-        MoveDir::Up => Quat::from_rotation_z(PI / 2.),
-        MoveDir::LeftUp => Quat::from_rotation_z(PI * 0.75),
-        MoveDir::Left => Quat::from_rotation_z(PI),
-        MoveDir::LeftDown => Quat::from_rotation_z(1.25 * PI),
-        MoveDir::Down => Quat::from_rotation_z(1.5 * PI),
-        MoveDir::RightDown => Quat::from_rotation_z(1.75 * PI)
+        ))
+            .with_children(|parent| {
+                // The label stays upright whichever way the arrow points.
+                parent.spawn(Text2dBundle {
+                    text: Text::from_section(operation.symbol(), TextStyle {
+                        font_size: ARROW_LABEL_FONT_SIZE,
+                        color: Color::BLACK,
+                        ..default()
+                    }),
+                    transform: Transform::from_rotation(Quat::from_rotation_z(-angle))
+                        .with_translation(Vec3::Z),
+                    ..default()
+                });
+            });
     }
+    event
 }
 
 fn send_arrow_selected(
@@ -99,17 +108,3 @@ fn send_arrow_selected(
 ) {
     ew.send(ArrowSelected(arrows.get(input.target).unwrap().0));
 }
-
-fn to_vec3(dir: MoveDir) -> Vec3 {
-    const D: f32 = 0.85;
-    match dir {
-        MoveDir::Down => Vec3::NEG_Y * D,
-        MoveDir::LeftUp => Vec3::new(-0.5, 0.5, 0.0),
-        MoveDir::Up => Vec3::Y * D,
-        MoveDir::RightUp => Vec3::new(0.5, 0.5, 0.0),
-        MoveDir::Left => Vec3::NEG_X * D,
-        MoveDir::Right => Vec3::X * D,
-        MoveDir::LeftDown => Vec3::new(-0.5, -0.5, 0.0),
-        MoveDir::RightDown => Vec3::new(0.5, -0.5, 0.0),
-    }
-}
@@ -1,10 +1,8 @@
 use std::time::Duration;
 
-use bevy::prelude::{Commands, Entity, EventWriter, In, Query, ResMut, With, Without};
+use bevy::prelude::{Commands, Entity, EventWriter, In, Query, Res, ResMut, With, Without};
 use bevy_flurx::prelude::*;
 
-use puzzle_core::calculator::Calculator;
-use puzzle_core::calculator::small_size::SmallSizeCalculator;
 use puzzle_core::move_dir::MoveDir;
 
 use crate::{CellAct, wait_tween_event};
@@ -55,12 +53,14 @@ fn wait_swap() -> ActionSeed {
 fn request_move(
     In(dir): In<MoveDir>,
     mut ew: EventWriter<RequestMove>,
+    stage: Res<PuzzleStage>,
     cell: Query<(Entity, &CellNo), With<MoveSource>>,
     others: Query<(Entity, &CellNo), Without<MoveSource>>,
 ) -> (Entity, Entity, MoveDir) {
     let (src, src_no) = cell.single();
+    let dist_no = stage.dist_no(src_no.0, dir).unwrap();
     for (dist, no) in others.iter() {
-        if no.0 == SmallSizeCalculator::dist_no::<4>(src_no.0, &dir).unwrap() {
+        if no.0 == dist_no {
             ew.send(RequestMove {
                 src,
                 dist,
@@ -5,29 +5,26 @@ use bevy::prelude::{Commands, Entity, EventWriter, Query, Res, ResMut, Resource}
 use bevy_flurx::action::{delay, OmitInput, once};
 use bevy_flurx::prelude::{ActionSeed, Then};
 
-use puzzle_core::answer::steps::Steps;
-use puzzle_core::calculator::Calculator;
-use puzzle_core::calculator::small_size::SmallSizeCalculator;
-
 use crate::action::move_cell::{play_move_se_if_release_mode, wait_move};
 use crate::action::setup_cells::reset_stage;
 use crate::plugin::move_cell::RequestMove;
-use crate::plugin::stage::{AnswerSteps, CellNo};
+use crate::plugin::stage::{AnswerSteps, CellNo, PuzzleStage};
 use crate::plugin::stage_clear::PlayAnswerMode;
 
+/// The index of the next step of [`AnswerSteps`] to play.
 #[derive(Resource)]
-struct TmpSteps(Steps);
+struct AnswerCursor(usize);
 
 pub fn setup_step_resource() -> ActionSeed {
     reset_stage()
-        .then(once::run(|mut commands: Commands, steps: Res<AnswerSteps>| {
-            commands.insert_resource(TmpSteps(steps.0.clone()));
+        .then(once::run(|mut commands: Commands| {
+            commands.insert_resource(AnswerCursor(0));
             commands.insert_resource(PlayAnswerMode);
         }))
 }
 
 pub fn exists_steps() -> ActionSeed<(), bool> {
-    once::run(|steps: Res<TmpSteps>| !steps.0.is_empty())
+    once::run(|cursor: Res<AnswerCursor>, steps: Res<AnswerSteps>| cursor.0 < steps.0.len())
 }
 
 pub fn play_next_step() -> ActionSeed {
@@ -40,12 +37,15 @@ pub fn play_next_step() -> ActionSeed {
 }
 
 fn next_step_move(
-    mut steps: ResMut<TmpSteps>,
+    mut cursor: ResMut<AnswerCursor>,
+    steps: Res<AnswerSteps>,
     mut ew: EventWriter<RequestMove>,
+    stage: Res<PuzzleStage>,
     cells: Query<(Entity, &CellNo)>,
 ) {
-    let (n, dir) = steps.0.pop_front().unwrap();
-    let Some(dist_no) = SmallSizeCalculator::dist_no::<4>(n, &dir) else{
+    let (n, dir) = steps.0[cursor.0];
+    cursor.0 += 1;
+    let Some(dist_no) = stage.dist_no(n, dir) else{
         return;
     };
     debug!("no: {n} move dir: {dir:?}");
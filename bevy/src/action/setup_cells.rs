@@ -2,7 +2,7 @@ use bevy::asset::Assets;
 use bevy::core::Name;
 use bevy::hierarchy::{BuildChildren, DespawnRecursiveExt};
 use bevy::math::{Vec2, Vec3};
-use bevy::prelude::{Color, ColorMaterial, Commands, default, Entity, EventWriter, Mesh, Or, Query, RegularPolygon, Res, ResMut, Text, Text2dBundle, TextStyle, Transform, With};
+use bevy::prelude::{Color, ColorMaterial, Commands, default, Entity, EventWriter, Mesh, Or, Query, Rectangle, RegularPolygon, Res, ResMut, Text, Text2dBundle, TextStyle, Transform, With};
 use bevy::sprite::MaterialMesh2dBundle;
 use bevy_flurx::action::{delay, once};
 use bevy_flurx::prelude::{ActionSeed, Then};
@@ -10,13 +10,18 @@ use bevy_mod_picking::events::{Down, Pointer};
 use bevy_mod_picking::PickableBundle;
 use bevy_mod_picking::prelude::{ListenerInput, On};
 
-use puzzle_core::answer::{AnswerInfo, generate_random_ratios};
+use puzzle_core::answer::{generate_stage_from_seed, random_seed};
 use puzzle_core::calculator::small_size::SmallSizeCalculator;
+use puzzle_core::number::PuzzleNumber;
 use puzzle_core::stage::Stage;
+use puzzle_core::topology::{CellPos, CellShape, Topology};
 
 use crate::arrow::remove_arrows;
 use crate::consts::{CELL_COLOR, PUZZLE_HALF, PUZZLE_MARGIN};
-use crate::plugin::stage::{Answer, AnswerSteps, CellNo, CellPanel, CellRatio, CellSelected, Moved, PuzzleStage, StageRatios};
+use crate::plugin::stage::{Answer, AnswerSteps, CellNo, CellPanel, CellRatio, CellSelected, Moved, NumberFormat, PuzzleStage, StageRatios, StageSeed};
+
+/// The distance between the centers of two cells one unit apart in [`Topology::position`].
+const CELL_SPACING: f32 = PUZZLE_HALF + PUZZLE_MARGIN;
 
 pub fn setup_cells() -> ActionSeed {
     once::run(setup_stage)
@@ -38,8 +43,10 @@ pub fn regenerate_stage() -> ActionSeed {
 fn generate_ratios(
     mut commands: Commands
 ) {
-    let ratios = generate_random_ratios::<4>();
-    let answer = AnswerInfo::generate::<4, SmallSizeCalculator>(ratios);
+    let seed = random_seed();
+    let (ratios, answer) = generate_stage_from_seed::<4, SmallSizeCalculator>(seed)
+        .expect("integers on the diamond board always have an answer");
+    commands.insert_resource(StageSeed(seed));
     commands.insert_resource(StageRatios(ratios));
     commands.insert_resource(Answer(answer.ratio));
     commands.insert_resource(AnswerSteps(answer.steps));
@@ -66,25 +73,18 @@ fn spawn_cells(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
     stage: Res<PuzzleStage>,
+    format: Res<NumberFormat>,
 ) {
-    const LEN: f32 = PUZZLE_HALF + PUZZLE_MARGIN;
-
-    for (i, (pos, ratio)) in [
-        Vec2::new(-LEN, 0.),
-        Vec2::new(0., LEN),
-        Vec2::new(0., -LEN),
-        Vec2::new(LEN, 0.),
-    ]
-        .iter()
-        .zip(stage.0.ratios())
-        .enumerate()
-    {
-        let pos = pos.extend(0.);
+    let shape = stage.topology().cell_shape();
+    for (i, ratio) in stage.0.ratios().into_iter().enumerate() {
+        let CellPos { x, y } = stage.topology().position(i);
+        let pos = (Vec2::new(x, y) * CELL_SPACING).extend(0.);
+        let label = ratio.as_ref().unwrap().display_with(format.0);
 
         commands.spawn((
             CellPanel,
             MaterialMesh2dBundle {
-                mesh: meshes.add(RegularPolygon::new(PUZZLE_HALF, 4)).into(),
+                mesh: meshes.add(cell_mesh(shape)).into(),
                 material: materials.add(Color::rgb(0.15, 0.15, 0.15)),
                 transform: Transform::from_translation(pos + Vec3::NEG_Z * 10.),
                 ..default()
@@ -94,7 +94,7 @@ fn spawn_cells(
         commands.spawn((
             PickableBundle::default(),
             MaterialMesh2dBundle {
-                mesh: meshes.add(RegularPolygon::new(PUZZLE_HALF, 4)).into(),
+                mesh: meshes.add(cell_mesh(shape)).into(),
                 material: materials.add(CELL_COLOR),
                 transform: Transform::from_translation(pos),
                 ..default()
@@ -106,7 +106,7 @@ fn spawn_cells(
             Name::new(format!("Cell{}", i + 1)),
         )).with_children(|parent| {
             parent.spawn(Text2dBundle {
-                text: Text::from_section(ratio.unwrap().to_string(), TextStyle {
+                text: Text::from_section(label, TextStyle {
                     color: Color::BLACK,
                     font_size: 40.,
                     ..default()
@@ -118,6 +118,15 @@ fn spawn_cells(
     }
 }
 
+/// Returns the mesh of a cell, sized so that neighbouring cells don't overlap.
+fn cell_mesh(shape: CellShape) -> Mesh {
+    match shape {
+        CellShape::Diamond => RegularPolygon::new(PUZZLE_HALF, 4).into(),
+        CellShape::Square => Rectangle::from_size(Vec2::splat(CELL_SPACING - PUZZLE_MARGIN)).into(),
+        CellShape::Hexagon => RegularPolygon::new((CELL_SPACING - PUZZLE_MARGIN) / 3f32.sqrt(), 6).into(),
+    }
+}
+
 fn send_cell_selected(
     mut ew: EventWriter<CellSelected>,
     listener: Res<ListenerInput<Pointer<Down>>>,
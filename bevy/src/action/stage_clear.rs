@@ -5,14 +5,17 @@ use bevy::prelude::{Commands, Entity, In, KeyCode, Query, Res, ResMut, With};
 use bevy_flurx::action::{delay, OmitInput, once, wait};
 use bevy_flurx::prelude::{ActionSeed, Pipe, Then};
 
+use crate::action::campaign::record_level_clear;
 use crate::consts::{GAME_CLEAR_COUNT, TWEEN_SHOW_TEXT};
+use crate::plugin::campaign::CampaignLevel;
 use crate::plugin::stage::CorrectAnswerNum;
 use crate::plugin::stage_clear::{PlayAnswerMode, RequestStageClear};
 use crate::plugin::stage_ui::StageClearText;
 use crate::wait_tween_event;
 
 pub fn stage_clear() -> ActionSeed {
-    once::run(update_answers)
+    once::run(record_level_clear)
+        .then(once::run(update_answers))
         .pipe(play_stage_clear_se_if_release_mode())
         .then(delay::time().with(Duration::from_millis(300)))
         .then(once::event::send().with(RequestStageClear))
@@ -27,11 +30,15 @@ fn update_answers(
     mut commands: Commands,
     mut num: ResMut<CorrectAnswerNum>,
     play_answer_mode: Option<Res<PlayAnswerMode>>,
+    campaign: Option<Res<CampaignLevel>>,
 ) -> &'static str {
     if play_answer_mode.is_some() {
         num.0 += 0;
         commands.remove_resource::<PlayAnswerMode>();
         "audio/stage_clear.ogg"
+    } else if campaign.is_some() {
+        // Campaign levels don't count toward the consecutive answers.
+        "audio/stage_clear.ogg"
     } else {
         num.0 += 1;
         if num.0 == GAME_CLEAR_COUNT {
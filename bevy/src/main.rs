@@ -10,6 +10,7 @@ use bevy_flurx::prelude::*;
 use bevy_mod_picking::DefaultPickingPlugins;
 use bevy_tweening::{TweenCompleted, TweeningPlugin};
 
+use crate::action::campaign::{leave_campaign, next_level, select_level, start_level};
 use crate::action::cell_select::select_cell;
 use crate::action::cleanup::cleanup;
 use crate::action::move_cell::move_cell;
@@ -88,19 +89,25 @@ fn spawn_reactor(mut commands: Commands) {
                         wait::input::just_pressed().with(KeyCode::KeyP),    // 4: play answer
                         wait::input::just_pressed().with(KeyCode::KeyZ),    // 5: undo
                         wait::input::just_pressed().with(KeyCode::KeyX),    // 6: redo
+                        wait::input::just_pressed().with(KeyCode::KeyC),    // 7: choose a campaign level
                     ]))
                     .through(cleanup())
             }).await;
 
             match end_action_index {
                 1 => {
-                    task.will(Update, stage_clear().then(regenerate_stage())).await;
+                    task.will(Update, stage_clear()).await;
+                    match task.will(Update, next_level()).await {
+                        Some(level) => task.will(Update, start_level().with(level)).await,
+                        None => task.will(Update, regenerate_stage()).await,
+                    }
                 }
                 2 => {
                     task.will(Update, reset_stage()).await;
                 }
                 3 => {
                     task.will(Update, once::run(reset_answers)
+                        .then(leave_campaign())
                         .then(regenerate_stage()),
                     ).await;
                 }
@@ -116,6 +123,11 @@ fn spawn_reactor(mut commands: Commands) {
                 6 => {
                     let _ = task.will(Update, record::redo::once::<CellAct>()).await;
                 }
+                7 => {
+                    if let Some(level) = task.will(Update, select_level()).await {
+                        task.will(Update, start_level().with(level)).await;
+                    }
+                }
                 _ => {}
             }
         }
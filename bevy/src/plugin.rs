@@ -1,16 +1,20 @@
 use bevy::app::{PluginGroup, PluginGroupBuilder};
 
+use crate::plugin::campaign::CampaignPlugin;
 use crate::plugin::move_cell::MoveCellPlugin;
 use crate::plugin::secret::SecretPlugin;
 use crate::plugin::stage::StagePlugin;
 use crate::plugin::stage_clear::StageClearPlugin;
 use crate::plugin::stage_ui::StageUiPlugin;
+use crate::plugin::unreachable::UnreachablePlugin;
 
+pub mod campaign;
 pub mod move_cell;
 pub mod stage;
 pub mod stage_clear;
 pub mod stage_ui;
-mod secret;
+pub mod unreachable;
+pub mod secret;
 
 
 pub struct PuzzlePlugins;
@@ -23,6 +27,8 @@ impl PluginGroup for PuzzlePlugins {
             .add(MoveCellPlugin)
             .add(StageClearPlugin)
             .add(SecretPlugin)
+            .add(UnreachablePlugin)
+            .add(CampaignPlugin)
             .build()
     }
 }
@@ -0,0 +1,258 @@
+use std::fmt::{Display, Formatter};
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bevy::app::{App, Plugin, PostStartup, Startup, Update};
+use bevy::asset::{Asset, AssetApp, AssetLoader, AssetServer, AsyncReadExt, Handle, LoadContext};
+use bevy::asset::io::file::FileAssetReader;
+use bevy::asset::io::Reader;
+use bevy::hierarchy::BuildChildren;
+use bevy::log::warn;
+use bevy::prelude::{Color, Commands, Component, Condition, Deref, Entity, IntoSystemConfigs, not, Query, Res, ResMut, Resource, resource_added, resource_changed, resource_exists, TextBundle, TextStyle, With};
+use bevy::reflect::TypePath;
+use bevy::text::{Text, TextSection};
+use bevy::time::{Stopwatch, Time};
+use bevy::utils::{BoxedFuture, default};
+use bevy_flurx::prelude::switch_turned_on;
+use serde::{Deserialize, Serialize};
+
+use puzzle_core::calculator::small_size::SmallSizeCalculator;
+use puzzle_core::notation::MismatchError;
+use puzzle_core::pack::{Pack, PACK_VERSION};
+
+use crate::plugin::stage_clear::InOperation;
+use crate::plugin::stage_ui::RightPanel;
+
+/// The pack played in campaign mode, relative to the assets directory.
+const CAMPAIGN_PACK_PATH: &str = "packs/campaign.pack.json";
+
+/// Where [`CampaignProgress`] is saved, relative to the assets directory.
+const CAMPAIGN_PROGRESS_PATH: &str = "packs/campaign.progress.json";
+
+/// A [`Pack`] loaded from a `.pack.json` file, as printed by `puzzle pack`.
+///
+/// Every stage has been checked to fit the diamond board.
+#[derive(Asset, TypePath, Debug, Deref)]
+pub struct PackAsset(pub Pack);
+
+/// The pack of the campaign; its stages are the levels, in order.
+#[derive(Resource, Debug)]
+pub struct Campaign {
+    pub pack: Handle<PackAsset>,
+}
+
+/// The level being played, present only while a campaign level is on the board.
+#[derive(Resource, Debug)]
+pub struct CampaignLevel {
+    pub index: usize,
+    /// The time spent on this level, including retries.
+    pub stopwatch: Stopwatch,
+}
+
+/// What the player has achieved on a level.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LevelRecord {
+    pub cleared: bool,
+    pub best_time: Option<Duration>,
+    pub best_moves: Option<usize>,
+}
+
+/// The records of every level, indexed like the stages of the pack.
+///
+/// Saved next to the campaign pack whenever it changes, and loaded again on startup.
+#[derive(Resource, Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CampaignProgress(Vec<LevelRecord>);
+
+impl CampaignProgress {
+    /// Reads the saved progress; starts from scratch if there is none or it can't be read.
+    pub fn load() -> Self {
+        let bytes = match std::fs::read(progress_path()) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Self::default(),
+            Err(e) => {
+                warn!("failed to read the campaign progress: {e}");
+                return Self::default();
+            }
+        };
+        serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+            warn!("ignoring the invalid campaign progress: {e}");
+            Self::default()
+        })
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        std::fs::write(progress_path(), serde_json::to_vec_pretty(self)?)
+    }
+
+    #[inline]
+    pub fn record(&self, level: usize) -> LevelRecord {
+        self.0.get(level).copied().unwrap_or_default()
+    }
+
+    /// The first level is always unlocked; every other one once the level before it is cleared.
+    #[inline]
+    pub fn is_unlocked(&self, level: usize) -> bool {
+        level == 0 || self.record(level - 1).cleared
+    }
+
+    /// Marks `level` as cleared and keeps the best time and move count separately.
+    pub fn record_clear(&mut self, level: usize, time: Duration, moves: usize) {
+        if self.0.len() <= level {
+            self.0.resize(level + 1, LevelRecord::default());
+        }
+        let record = &mut self.0[level];
+        record.cleared = true;
+        record.best_time = Some(record.best_time.map_or(time, |best| best.min(time)));
+        record.best_moves = Some(record.best_moves.map_or(moves, |best| best.min(moves)));
+    }
+}
+
+#[derive(Debug)]
+pub enum PackLoadError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Version(u32),
+    /// The stage at `index` can't be played on the diamond board.
+    Stage {
+        index: usize,
+        error: MismatchError,
+    },
+}
+
+impl Display for PackLoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PackLoadError::Io(e) => write!(f, "failed to read the pack: {e}"),
+            PackLoadError::Json(e) => write!(f, "invalid pack: {e}"),
+            PackLoadError::Version(version) => write!(f, "unsupported pack version {version}; expected {PACK_VERSION}"),
+            PackLoadError::Stage { index, error } => write!(f, "stage {} can't be played: {error}", index + 1),
+        }
+    }
+}
+
+impl std::error::Error for PackLoadError {}
+
+#[derive(Default)]
+struct PackLoader;
+
+impl AssetLoader for PackLoader {
+    type Asset = PackAsset;
+    type Settings = ();
+    type Error = PackLoadError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _: &'a Self::Settings,
+        _: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await.map_err(PackLoadError::Io)?;
+            let pack: Pack = serde_json::from_slice(&bytes).map_err(PackLoadError::Json)?;
+            if pack.version != PACK_VERSION {
+                return Err(PackLoadError::Version(pack.version));
+            }
+            for (index, notation) in pack.stages.iter().enumerate() {
+                notation
+                    .to_stage::<4, _>(SmallSizeCalculator)
+                    .map_err(|error| PackLoadError::Stage { index, error })?;
+            }
+            Ok(PackAsset(pack))
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["pack.json"]
+    }
+}
+
+#[derive(Component)]
+struct LevelText;
+
+/// Loads the campaign pack and keeps the records of its levels.
+///
+/// The levels themselves are chosen and started by [`crate::action::campaign`].
+pub struct CampaignPlugin;
+
+impl Plugin for CampaignPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_asset::<PackAsset>()
+            .init_asset_loader::<PackLoader>()
+            .insert_resource(CampaignProgress::load())
+            .add_systems(Startup, load_campaign)
+            .add_systems(PostStartup, spawn_level_text)
+            .add_systems(Update, (
+                tick_level_stopwatch
+                    .run_if(switch_turned_on::<InOperation>)
+                    .run_if(resource_exists::<CampaignLevel>),
+                update_level_text,
+                save_progress.run_if(resource_changed::<CampaignProgress>.and_then(not(resource_added::<CampaignProgress>))),
+            ));
+    }
+}
+
+fn progress_path() -> PathBuf {
+    FileAssetReader::get_base_path().join("assets").join(CAMPAIGN_PROGRESS_PATH)
+}
+
+fn save_progress(progress: Res<CampaignProgress>) {
+    if let Err(e) = progress.save() {
+        warn!("failed to save the campaign progress: {e}");
+    }
+}
+
+fn load_campaign(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    commands.insert_resource(Campaign {
+        pack: asset_server.load(CAMPAIGN_PACK_PATH),
+    });
+}
+
+fn spawn_level_text(
+    mut commands: Commands,
+    right_panel: Query<Entity, With<RightPanel>>,
+) {
+    let id = commands.spawn((
+        LevelText,
+        TextBundle {
+            text: Text::from_sections([
+                TextSection::new("level: ", TextStyle {
+                    font_size: 32.,
+                    ..default()
+                }),
+                TextSection::new("-", TextStyle {
+                    font_size: 32.,
+                    color: Color::GOLD,
+                    ..default()
+                }),
+            ]),
+            ..default()
+        }
+    ))
+        .id();
+    commands.entity(right_panel.single()).add_child(id);
+}
+
+fn tick_level_stopwatch(
+    mut level: ResMut<CampaignLevel>,
+    time: Res<Time>,
+) {
+    level.stopwatch.tick(time.delta());
+}
+
+fn update_level_text(
+    mut text: Query<&mut Text, With<LevelText>>,
+    level: Option<Res<CampaignLevel>>,
+) {
+    let value = level.map_or_else(|| String::from("-"), |level| format!("{}", level.index + 1));
+    for mut text in text.iter_mut() {
+        if text.sections[1].value != value {
+            text.sections[1].value.clone_from(&value);
+        }
+    }
+}
@@ -5,6 +5,7 @@ use bevy::prelude::{Commands, Component, Entity, Event, EventReader, EventWriter
 use bevy_tweening::{Animator, EaseMethod, Tween, TweenCompleted};
 use bevy_tweening::lens::TransformPositionLens;
 use puzzle_core::move_dir::MoveDir;
+use puzzle_core::stage::history::MoveKind;
 
 
 use crate::consts::{TWEEN_COMBINE_SRC, TWEEN_SWAP_DIST, TWEEN_SWAP_SRC};
@@ -64,19 +65,17 @@ fn request_move(
     cells: Query<&CellNo>,
 ) {
     for RequestMove { src, dist, dir } in er.read().copied() {
-        stage.move_cell(cells.get(src).unwrap().0, dir);
-        match dir {
-            MoveDir::Up |
-            MoveDir::Down |
-            MoveDir::Left |
-            MoveDir::Right
-            => {
+        let Ok(outcome) = stage.move_cell(cells.get(src).unwrap().0, dir) else {
+            continue;
+        };
+        match outcome.kind() {
+            MoveKind::Swap => {
                 swap.send(StartSwap {
                     src,
                     dist,
                 });
             }
-            _ => {
+            MoveKind::Combine => {
                 combine.send(StartCombine {
                     src,
                     dist,
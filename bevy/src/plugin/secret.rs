@@ -19,15 +19,19 @@ pub struct SecretStopWatch(Stopwatch);
 
 impl SecretStopWatch {
     pub fn as_format_text(&self) -> String {
-        let elapsed = self.0.elapsed_secs_f64();
-        let hour = (elapsed / 60. / 60.) as u64;
-        let minutes = (elapsed / 60. % 60.) as u64;
-        let secs = (elapsed % 60.) as u64;
-
-        format!("{hour:02}:{minutes:02}:{secs:02}")
+        format_time(self.0.elapsed_secs_f64())
     }
 }
 
+/// Formats `elapsed` seconds as `hh:mm:ss`.
+pub fn format_time(elapsed: f64) -> String {
+    let hour = (elapsed / 60. / 60.) as u64;
+    let minutes = (elapsed / 60. % 60.) as u64;
+    let secs = (elapsed % 60.) as u64;
+
+    format!("{hour:02}:{minutes:02}:{secs:02}")
+}
+
 #[derive(Component)]
 struct TimeText;
 
@@ -1,20 +1,29 @@
 use bevy::app::{App, Plugin, PreUpdate, Update};
 use bevy::asset::{Handle};
 use bevy::core::Name;
-use bevy::prelude::{Assets, Color, ColorMaterial, Component, Deref, DerefMut, Entity, Event, Parent, Query, Reflect, ReflectComponent, Res, ResMut, Resource, Visibility};
+use bevy::input::ButtonInput;
+use bevy::prelude::{Assets, Color, ColorMaterial, Component, Deref, DerefMut, Entity, Event, KeyCode, Parent, Query, Reflect, ReflectComponent, Res, ResMut, Resource, Visibility};
 use bevy::text::Text;
 use puzzle_core::answer::steps::Steps;
 use puzzle_core::calculator::small_size::SmallSizeCalculator;
 use puzzle_core::move_dir::MoveDir;
-
-
+use puzzle_core::number::PuzzleNumber;
 use puzzle_core::ratio::Ratio;
+use puzzle_core::ratio::format::RatioFormat;
 use puzzle_core::stage::RatioArray;
 
 use crate::consts::CELL_COLOR;
 
+/// The value type of the cells, written with [`PuzzleNumber::display_with`].
+///
+/// Besides [`PuzzleNumber`], the components and resources below need it to be `Copy`, `Default` and [`Reflect`];
+/// of the numbers in `puzzle_core`, only [`Ratio`] and `isize` are.
+/// Stages are also generated with [`puzzle_core::answer::generate_stage_from_seed`], which yields [`Ratio`]s,
+/// so another type needs a [`puzzle_core::answer::pool::NumberPool`] of its own in `setup_cells` too.
+pub type CellNumber = Ratio;
+
 #[derive(Resource, Debug, Deref, DerefMut, Default)]
-pub struct PuzzleStage(pub puzzle_core::stage::Stage<4, SmallSizeCalculator>);
+pub struct PuzzleStage(pub puzzle_core::stage::Stage<4, SmallSizeCalculator, CellNumber>);
 
 #[derive(Component)]
 pub struct MoveSource;
@@ -35,24 +44,36 @@ pub struct CellSelected(pub Entity, pub usize);
 
 #[derive(Component, Debug, Copy, Clone, Eq, PartialEq, Reflect)]
 #[reflect(Component)]
-pub struct CellRatio(pub Option<Ratio>);
+pub struct CellRatio(pub Option<CellNumber>);
 
 #[derive(Component, Debug, Copy, Clone, Eq, PartialEq, Reflect)]
 #[reflect(Component)]
 pub struct Moved(pub bool);
 
 #[derive(Resource, Debug, Copy, Clone, Eq, PartialEq, Reflect)]
-pub struct StageRatios(pub RatioArray<4>);
+pub struct StageRatios(pub RatioArray<4, CellNumber>);
 
 #[derive(Resource, Debug, Copy, Clone, Eq, PartialEq, Reflect, Default)]
-pub struct Answer(pub Ratio);
+pub struct Answer(pub CellNumber);
 
 #[derive(Resource, Debug, Clone, Eq, PartialEq)]
 pub struct AnswerSteps(pub Steps);
 
+/// The seed the current stage was generated from.
+///
+/// Passing it to [`puzzle_core::answer::generate_stage_from_seed`] reproduces the same stage.
+#[derive(Resource, Debug, Copy, Clone, Eq, PartialEq, Reflect, Default)]
+pub struct StageSeed(pub u64);
+
 #[derive(Resource, Debug, Clone, Eq, PartialEq, Default)]
 pub struct CorrectAnswerNum(pub u64);
 
+/// How the cells and the goal are written; [F] switches to the next format.
+///
+/// The default font only has ASCII glyphs, so [`RatioFormat::Vulgar`] needs a font with Unicode fractions.
+#[derive(Resource, Debug, Copy, Clone, Eq, PartialEq, Reflect, Default, Deref)]
+pub struct NumberFormat(pub RatioFormat);
+
 pub struct StagePlugin;
 
 impl Plugin for StagePlugin {
@@ -66,9 +87,12 @@ impl Plugin for StagePlugin {
             .register_type::<CellPanel>()
             .init_resource::<PuzzleStage>()
             .init_resource::<Answer>()
+            .init_resource::<StageSeed>()
+            .init_resource::<NumberFormat>()
             .insert_resource(CorrectAnswerNum(0))
             .add_systems(PreUpdate, update_cell_status)
             .add_systems(Update, (
+                switch_number_format,
                 update_cell_texts,
                 update_cell_colors,
             ));
@@ -87,13 +111,23 @@ fn update_cell_status(
     }
 }
 
+fn switch_number_format(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut format: ResMut<NumberFormat>,
+) {
+    if keys.just_pressed(KeyCode::KeyF) {
+        format.0 = format.next();
+    }
+}
+
 fn update_cell_texts(
     cell: Query<&CellRatio>,
     mut cell_text: Query<(&Parent, &mut Text)>,
+    format: Res<NumberFormat>,
 ) {
     for (parent, mut text) in cell_text.iter_mut() {
         if let Ok(ratio) = cell.get(parent.get()) {
-            text.sections[0].value = ratio.0.map(|r| r.to_string()).unwrap_or_default();
+            text.sections[0].value = ratio.0.as_ref().map(|r| r.display_with(format.0)).unwrap_or_default();
         }
     }
 }
@@ -11,6 +11,7 @@ use bevy_tweening::{Animator, EaseFunction, Tween};
 use bevy_tweening::lens::TransformScaleLens;
 
 use crate::consts::{GAME_CLEAR_COUNT, TWEEN_SHOW_TEXT};
+use crate::plugin::campaign::CampaignLevel;
 use crate::plugin::secret::SecretStopWatch;
 use crate::plugin::stage::{Answer, CellRatio, CorrectAnswerNum};
 use crate::plugin::stage_ui::StageClearText;
@@ -75,6 +76,7 @@ fn start_stage_clear_animation(
     mut commands: Commands,
     answers: Res<CorrectAnswerNum>,
     stop_watch: Res<SecretStopWatch>,
+    campaign: Option<Res<CampaignLevel>>,
 ) {
     commands.spawn((
         StageClearText,
@@ -102,10 +104,12 @@ fn start_stage_clear_animation(
             )
                 .with_completed_event(TWEEN_SHOW_TEXT);
 
-            let message = if answers.0 == GAME_CLEAR_COUNT {
+            let message = if campaign.is_some() {
+                stage_clear_message(next_level_message())
+            } else if answers.0 == GAME_CLEAR_COUNT {
                 secret_message(stop_watch.as_format_text())
             } else {
-                stage_clear_message()
+                stage_clear_message(generate_next_stage_message())
             };
             parent.spawn((
                 Animator::new(tween),
@@ -114,11 +118,11 @@ fn start_stage_clear_animation(
         });
 }
 
-fn stage_clear_message() -> TextBundle {
+fn stage_clear_message(next: TextSection) -> TextBundle {
     TextBundle {
         text: Text::from_sections([
             TextSection::new("Stage Clear\n\n", cleat_message_style()),
-            generate_next_stage_message(),
+            next,
         ]).with_justify(JustifyText::Center),
         transform: Transform::from_scale(Vec3::ZERO),
         ..default()
@@ -147,6 +151,10 @@ fn generate_next_stage_message() -> TextSection {
     TextSection::new("[G]: Generate next stage", message_style())
 }
 
+fn next_level_message() -> TextSection {
+    TextSection::new("[G]: Next level", message_style())
+}
+
 fn cleat_message_style() -> TextStyle {
     TextStyle {
         font_size: 120.,
@@ -1,12 +1,13 @@
 use bevy::app::{App, Plugin, PreStartup, Startup};
 use bevy::hierarchy::BuildChildren;
-use bevy::prelude::{Color, Commands, Component, Entity, IntoSystemConfigs, NodeBundle, PositionType, Query, Reflect, ReflectComponent, Res, resource_exists_and_changed, Text, TextBundle, Update, Val, With};
+use bevy::prelude::{Color, Commands, Component, Condition, Entity, IntoSystemConfigs, NodeBundle, PositionType, Query, Reflect, ReflectComponent, Res, resource_changed, resource_exists_and_changed, Text, TextBundle, Update, Val, With};
 use bevy::text::{TextSection, TextStyle};
 use bevy::ui::{Display, FlexDirection, Style};
 use bevy::utils::default;
 use bevy_mod_picking::picking_core::Pickable;
+use puzzle_core::number::PuzzleNumber;
 
-use crate::plugin::stage::{Answer, CorrectAnswerNum};
+use crate::plugin::stage::{Answer, CorrectAnswerNum, NumberFormat, StageSeed};
 
 #[derive(Copy, Clone, Component, Reflect, Debug, Eq, PartialEq)]
 #[reflect(Component)]
@@ -24,6 +25,10 @@ struct AnswerText;
 #[reflect(Component)]
 struct CorrectAnswerNumText;
 
+#[derive(Debug, Default, Copy, Clone, Component, Reflect, Eq, PartialEq)]
+#[reflect(Component)]
+struct SeedText;
+
 #[derive(Debug, Default, Copy, Clone, Component, Reflect, Eq, PartialEq)]
 #[reflect(Component)]
 pub struct StageClearText;
@@ -36,6 +41,7 @@ impl Plugin for StageUiPlugin {
             .register_type::<RootUi>()
             .register_type::<AnswerText>()
             .register_type::<CorrectAnswerNumText>()
+            .register_type::<SeedText>()
             .register_type::<StageClearText>()
             .register_type::<RightPanel>()
             .add_systems(PreStartup, spawn_root_ui)
@@ -44,8 +50,9 @@ impl Plugin for StageUiPlugin {
                 spawn_right_panel
             ).chain())
             .add_systems(Update, (
-                update_answer_text.run_if(resource_exists_and_changed::<Answer>),
+                update_answer_text.run_if(resource_exists_and_changed::<Answer>.or_else(resource_changed::<NumberFormat>)),
                 update_correct_answer_num_text.run_if(resource_exists_and_changed::<CorrectAnswerNum>),
+                update_seed_text.run_if(resource_exists_and_changed::<StageSeed>),
             ));
     }
 }
@@ -99,6 +106,10 @@ fn spawn_input_label(
                 text: Text::from_section("[G]: Generate a new stage", text_style.clone()),
                 ..default()
             });
+            parent.spawn(TextBundle {
+                text: Text::from_section("[C]: Campaign", text_style.clone()),
+                ..default()
+            });
             parent.spawn(TextBundle {
                 text: Text::from_section("[P]: Play answer", text_style.clone()),
                 ..default()
@@ -108,7 +119,11 @@ fn spawn_input_label(
                 ..default()
             });
             parent.spawn(TextBundle {
-                text: Text::from_section("[X]: Redo", text_style),
+                text: Text::from_section("[X]: Redo", text_style.clone()),
+                ..default()
+            });
+            parent.spawn(TextBundle {
+                text: Text::from_section("[F]: Number format", text_style),
                 ..default()
             });
         })
@@ -176,6 +191,24 @@ fn spawn_right_panel(
                     ..default()
                 }
             ));
+
+            parent.spawn((
+                SeedText,
+                TextBundle {
+                    text: Text::from_sections([
+                        TextSection::new("seed: ", TextStyle {
+                            font_size: LABEL_FONT_SIZE,
+                            ..default()
+                        }),
+                        TextSection::new("", TextStyle {
+                            font_size: LABEL_FONT_SIZE,
+                            color: Color::GOLD,
+                            ..default()
+                        })
+                    ]),
+                    ..default()
+                }
+            ));
         })
         .id();
 
@@ -186,10 +219,11 @@ fn spawn_right_panel(
 
 fn update_answer_text(
     answer: Res<Answer>,
+    format: Res<NumberFormat>,
     mut text: Query<&mut Text, With<AnswerText>>,
 ) {
     for mut text in text.iter_mut() {
-        text.sections[1].value = format!("{}", answer.0);
+        text.sections[1].value = answer.0.display_with(format.0);
     }
 }
 
@@ -201,3 +235,12 @@ fn update_correct_answer_num_text(
         text.sections[1].value = format!("{}", num.0);
     }
 }
+
+fn update_seed_text(
+    mut text: Query<&mut Text, With<SeedText>>,
+    seed: Res<StageSeed>,
+) {
+    for mut text in text.iter_mut() {
+        text.sections[1].value = format!("{}", seed.0);
+    }
+}
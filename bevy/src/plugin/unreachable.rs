@@ -0,0 +1,78 @@
+use bevy::app::{App, Plugin, Update};
+use bevy::hierarchy::{BuildChildren, DespawnRecursiveExt};
+use bevy::prelude::{BackgroundColor, Color, Commands, Component, default, Display, Entity, IntoSystemConfigs, JustifyText, NodeBundle, Query, Reflect, ReflectComponent, Res, resource_exists_and_changed, TextBundle, TextSection, With};
+use bevy::text::{Text, TextStyle};
+use bevy::ui::{AlignItems, JustifyContent, Style, Val};
+
+use crate::plugin::stage::{Answer, PuzzleStage};
+
+/// The overlay shown while the goal can no longer be reached.
+#[derive(Debug, Default, Copy, Clone, Component, Reflect, Eq, PartialEq)]
+#[reflect(Component)]
+pub struct UnreachableText;
+
+/// Checks whether the goal is still reachable every time [`PuzzleStage`] changes,
+/// and shows [`UnreachableText`] while it isn't.
+///
+/// The overlay disappears by itself when the stage is reset or the last move is undone.
+pub struct UnreachablePlugin;
+
+impl Plugin for UnreachablePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .register_type::<UnreachableText>()
+            .add_systems(Update, update_unreachable_text.run_if(resource_exists_and_changed::<PuzzleStage>));
+    }
+}
+
+fn update_unreachable_text(
+    mut commands: Commands,
+    stage: Res<PuzzleStage>,
+    answer: Res<Answer>,
+    texts: Query<Entity, With<UnreachableText>>,
+) {
+    // The stage is empty until the first stage has been set up.
+    let unreachable = stage.ratios().iter().any(Option::is_some) && !stage.is_reachable(answer.0);
+    if unreachable && texts.is_empty() {
+        spawn_unreachable_text(&mut commands);
+    } else if !unreachable {
+        for entity in texts.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn spawn_unreachable_text(commands: &mut Commands) {
+    commands.spawn((
+        UnreachableText,
+        NodeBundle {
+            style: Style {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                display: Display::Flex,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            background_color: BackgroundColor(Color::BLACK.with_a(0.5)),
+            ..default()
+        }
+    ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                text: Text::from_sections([
+                    TextSection::new("No way to reach the goal\n\n", TextStyle {
+                        font_size: 80.,
+                        color: Color::ORANGE_RED,
+                        ..default()
+                    }),
+                    TextSection::new("[R]: Retry this stage\n[Z]: Undo", TextStyle {
+                        font_size: 48.,
+                        color: Color::ORANGE_RED,
+                        ..default()
+                    }),
+                ]).with_justify(JustifyText::Center),
+                ..default()
+            });
+        });
+}
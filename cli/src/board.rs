@@ -0,0 +1,70 @@
+use clap::ValueEnum;
+use puzzle_core::calculator::Calculator;
+use puzzle_core::calculator::grid::GridCalculator;
+use puzzle_core::calculator::hex::HexCalculator;
+use puzzle_core::calculator::small_size::SmallSizeCalculator;
+use puzzle_core::topology::Topology;
+
+/// The boards the tool can work with.
+///
+/// Boards are chosen at compile time in `puzzle_core`, so every board needs a variant here.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
+pub enum Board {
+    Diamond,
+    Grid3x2,
+    Grid3x3,
+    Hex2x2,
+    Hex3x2,
+}
+
+impl Board {
+    /// Returns the board whose [`Topology::name`] is `name`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Board::value_variants()
+            .iter()
+            .copied()
+            .find(|board| board.name() == name)
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Board::Diamond => SmallSizeCalculator.topology().name(),
+            Board::Grid3x2 => GridCalculator::<3, 2>.topology().name(),
+            Board::Grid3x3 => GridCalculator::<3, 3>.topology().name(),
+            Board::Hex2x2 => HexCalculator::<2, 2>.topology().name(),
+            Board::Hex3x2 => HexCalculator::<3, 2>.topology().name(),
+        }
+    }
+}
+
+/// Calls the generic function `$f::<STAGE_SIZE, Calc>($args)` for the board's stage size and calculator.
+macro_rules! with_board {
+    ($board:expr, $f:ident($($arg:expr),* $(,)?)) => {
+        match $board {
+            $crate::board::Board::Diamond => $f::<4, puzzle_core::calculator::small_size::SmallSizeCalculator>($($arg),*),
+            $crate::board::Board::Grid3x2 => $f::<6, puzzle_core::calculator::grid::GridCalculator<3, 2>>($($arg),*),
+            $crate::board::Board::Grid3x3 => $f::<9, puzzle_core::calculator::grid::GridCalculator<3, 3>>($($arg),*),
+            $crate::board::Board::Hex2x2 => $f::<4, puzzle_core::calculator::hex::HexCalculator<2, 2>>($($arg),*),
+            $crate::board::Board::Hex3x2 => $f::<6, puzzle_core::calculator::hex::HexCalculator<3, 2>>($($arg),*),
+        }
+    };
+}
+
+pub(crate) use with_board;
+
+#[cfg(test)]
+mod tests {
+    use clap::ValueEnum;
+
+    use crate::board::Board;
+
+    #[test]
+    fn from_name() {
+        for board in Board::value_variants() {
+            assert_eq!(Board::from_name(&board.name()), Some(*board));
+        }
+        assert_eq!(Board::from_name("diamond"), Some(Board::Diamond));
+        assert_eq!(Board::from_name("grid 3x2"), Some(Board::Grid3x2));
+        assert_eq!(Board::from_name("triangle"), None);
+    }
+}
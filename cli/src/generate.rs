@@ -0,0 +1,294 @@
+use clap::{Args, ValueEnum};
+use puzzle_core::answer::constraints::{ConstraintError, DEFAULT_ATTEMPTS, GeneratorConstraints};
+use puzzle_core::answer::pool::{NumberPool, PoolError};
+use puzzle_core::answer::{generate_constrained_stage_from_seed, GenerateError, random_seed};
+use puzzle_core::calculator::Calculator;
+use puzzle_core::difficulty::{Difficulty, DifficultyBand};
+use puzzle_core::notation::Notation;
+use puzzle_core::ratio::Ratio;
+
+use crate::board::{Board, with_board};
+
+/// The number of seeds tried for each puzzle before giving up on the requested difficulty.
+const ATTEMPTS_PER_PUZZLE: u64 = 1000;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
+pub enum DifficultyArg {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl From<DifficultyArg> for DifficultyBand {
+    fn from(value: DifficultyArg) -> Self {
+        match value {
+            DifficultyArg::Easy => DifficultyBand::Easy,
+            DifficultyArg::Medium => DifficultyBand::Medium,
+            DifficultyArg::Hard => DifficultyBand::Hard,
+        }
+    }
+}
+
+/// The goal constraints shared by `generate` and `pack`.
+#[derive(Debug, Clone, Args)]
+pub struct ConstraintArgs {
+    /// Only integer goals.
+    #[arg(long)]
+    pub integer_goal: bool,
+    /// The smallest goal, inclusive.
+    #[arg(long, allow_negative_numbers = true)]
+    pub min_goal: Option<isize>,
+    /// The largest goal, inclusive.
+    #[arg(long, allow_negative_numbers = true)]
+    pub max_goal: Option<isize>,
+    /// No negative goals.
+    #[arg(long)]
+    pub non_negative_goal: bool,
+    /// No goal equal to one of the initial cells.
+    #[arg(long)]
+    pub new_goal: bool,
+    /// The smallest number of combines in the answer.
+    #[arg(long, default_value_t = 0)]
+    pub min_combines: usize,
+    /// The number of answers tried on each seed before moving on to the next.
+    #[arg(long, default_value_t = DEFAULT_ATTEMPTS)]
+    pub attempts: usize,
+}
+
+impl From<ConstraintArgs> for GeneratorConstraints {
+    fn from(value: ConstraintArgs) -> Self {
+        Self {
+            integer_goal: value.integer_goal,
+            min_goal: value.min_goal.map(Ratio::from),
+            max_goal: value.max_goal.map(Ratio::from),
+            non_negative_goal: value.non_negative_goal,
+            new_goal: value.new_goal,
+            min_combines: value.min_combines,
+            max_attempts: value.attempts,
+        }
+    }
+}
+
+/// The starting values shared by `generate` and `pack`.
+///
+/// Without any of them, distinct integers from 1 to 10 are drawn, or up to the number of cells on larger boards.
+#[derive(Debug, Clone, Args)]
+pub struct PoolArgs {
+    /// The smallest starting value; 1 if omitted.
+    #[arg(long, allow_negative_numbers = true)]
+    pub min_value: Option<isize>,
+    /// The largest starting value; 10 if omitted.
+    #[arg(long, allow_negative_numbers = true)]
+    pub max_value: Option<isize>,
+    /// Lets several cells start with the same value.
+    #[arg(long)]
+    pub duplicates: bool,
+    /// Also draws fractions with denominators up to this.
+    #[arg(long)]
+    pub max_denom: Option<isize>,
+    /// Draws only fractions.
+    #[arg(long, requires = "max_denom")]
+    pub fractions_only: bool,
+}
+
+impl PoolArgs {
+    /// Returns the pool, or `None` for the default one of the board.
+    pub fn to_pool(&self) -> Result<Option<NumberPool>, PoolError> {
+        if self.min_value.is_none() && self.max_value.is_none() && !self.duplicates && self.max_denom.is_none() {
+            return Ok(None);
+        }
+        let (min, max) = (self.min_value.unwrap_or(1), self.max_value.unwrap_or(10));
+        let fractions = self.max_denom.map(|denom| NumberPool::fractions(min, max, denom)).transpose()?;
+        let pool = match fractions {
+            Some(fractions) if self.fractions_only => fractions,
+            Some(fractions) => NumberPool::range(min, max)?.chain(fractions),
+            None => NumberPool::range(min, max)?,
+        };
+        Ok(Some(pool.with_duplicates(self.duplicates)))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GenerateOptions {
+    pub count: usize,
+    /// The seed of the first puzzle; the following seeds are tried in order.
+    pub seed: Option<u64>,
+    pub board: Board,
+    pub difficulty: Option<DifficultyBand>,
+    pub author: Option<String>,
+    /// The values the cells start with; the default of the board if `None`.
+    pub pool: Option<NumberPool>,
+    pub constraints: GeneratorConstraints,
+}
+
+/// Generates `count` puzzles, each with the seed that reproduces it.
+///
+/// Seeds are tried one by one from `seed`, skipping puzzles of other difficulties
+/// and seeds whose puzzle can't meet the constraints,
+/// so the same options always yield the same puzzles.
+pub fn generate(options: &GenerateOptions) -> Result<Vec<Notation>, String> {
+    with_board!(options.board, generate_on(options))
+}
+
+fn generate_on<const STAGE_SIZE: usize, Calc>(options: &GenerateOptions) -> Result<Vec<Notation>, String>
+    where Calc: Calculator + Default + Clone + 'static
+{
+    let pool = options.pool.clone().unwrap_or_else(|| NumberPool::for_cells(STAGE_SIZE));
+    let mut seed = options.seed.unwrap_or_else(random_seed);
+    let mut puzzles = Vec::with_capacity(options.count);
+    while puzzles.len() < options.count {
+        let mut attempts = 0;
+        let notation = loop {
+            if ATTEMPTS_PER_PUZZLE <= attempts {
+                return Err(format!("no puzzle found within {ATTEMPTS_PER_PUZZLE} seeds; try another difficulty or looser constraints"));
+            }
+            let generated = generate_constrained_stage_from_seed::<STAGE_SIZE, Calc, _>(seed, &pool, &options.constraints);
+            let puzzle_seed = seed;
+            seed = seed.wrapping_add(1);
+            attempts += 1;
+            let (ratios, answer) = match generated {
+                Ok(stage) => stage,
+                Err(GenerateError::Constraints(ConstraintError::GaveUp { .. })) => continue,
+                Err(e) => return Err(e.to_string()),
+            };
+            let band = answer.difficulty.as_ref().map(Difficulty::band);
+            if options.difficulty.is_some() && band != options.difficulty {
+                if band.is_none() {
+                    return Err(format!("puzzles on the {} board are too large to be rated", options.board.name()));
+                }
+                continue;
+            }
+            let mut notation = Notation::new(Calc::default().topology(), &ratios, answer.ratio, answer.steps);
+            notation.seed = Some(puzzle_seed);
+            notation.author.clone_from(&options.author);
+            notation.difficulty = band;
+            break notation;
+        };
+        puzzles.push(notation);
+    }
+    Ok(puzzles)
+}
+
+#[cfg(test)]
+mod tests {
+    use puzzle_core::answer::constraints::GeneratorConstraints;
+    use puzzle_core::answer::pool::NumberPool;
+    use puzzle_core::difficulty::DifficultyBand;
+    use puzzle_core::ratio::Ratio;
+
+    use crate::board::Board;
+    use crate::generate::{generate, GenerateOptions};
+    use crate::validate::validate;
+
+    #[test]
+    fn reproducible() {
+        let options = GenerateOptions {
+            count: 3,
+            seed: Some(1),
+            board: Board::Diamond,
+            difficulty: None,
+            author: None,
+            pool: None,
+            constraints: GeneratorConstraints::default(),
+        };
+        let puzzles = generate(&options).unwrap();
+        assert_eq!(puzzles.len(), 3);
+        assert_eq!(puzzles, generate(&options).unwrap());
+        assert_eq!(puzzles.iter().map(|p| p.seed).collect::<Vec<_>>(), [Some(1), Some(2), Some(3)]);
+        assert!(puzzles.iter().all(|p| validate(p).is_ok()));
+    }
+
+    #[test]
+    fn difficulty() {
+        let options = GenerateOptions {
+            count: 2,
+            seed: Some(5),
+            board: Board::Diamond,
+            difficulty: Some(DifficultyBand::Easy),
+            author: Some(String::from("designer")),
+            pool: None,
+            constraints: GeneratorConstraints::default(),
+        };
+        for puzzle in generate(&options).unwrap() {
+            assert_eq!(puzzle.difficulty, Some(DifficultyBand::Easy));
+            assert_eq!(puzzle.author.as_deref(), Some("designer"));
+        }
+    }
+
+    #[test]
+    fn constraints() {
+        let mut options = GenerateOptions {
+            count: 5,
+            seed: Some(1),
+            board: Board::Diamond,
+            difficulty: None,
+            author: None,
+            pool: None,
+            constraints: GeneratorConstraints {
+                integer_goal: true,
+                non_negative_goal: true,
+                new_goal: true,
+                ..Default::default()
+            },
+        };
+        for puzzle in generate(&options).unwrap() {
+            assert!(puzzle.goal.to_integer().is_some_and(|goal| 0 <= goal));
+            assert!(!puzzle.cells.contains(&puzzle.goal));
+        }
+        options.constraints.min_goal = Some(Ratio::from(10));
+        options.constraints.max_goal = Some(Ratio::from(5));
+        assert_eq!(generate(&options), Err(String::from("the goal constraints can't be met by any answer")));
+    }
+
+    #[test]
+    fn pool() {
+        let mut options = GenerateOptions {
+            count: 3,
+            seed: Some(1),
+            board: Board::Diamond,
+            difficulty: None,
+            author: None,
+            pool: Some(NumberPool::range(-3, 0).unwrap().with_duplicates(true)),
+            constraints: GeneratorConstraints::default(),
+        };
+        for puzzle in generate(&options).unwrap() {
+            assert!(puzzle.cells.iter().all(|c| (-3..=0).contains(&c.numer) && c.denom == 1));
+            assert!(validate(&puzzle).is_ok());
+        }
+        options.pool = Some(NumberPool::range(1, 3).unwrap());
+        assert_eq!(
+            generate(&options),
+            Err(String::from("the number pool has 3 distinct values for 4 cells; allow duplicates or widen it"))
+        );
+    }
+
+    #[test]
+    fn other_boards() {
+        let options = GenerateOptions {
+            count: 1,
+            seed: Some(1),
+            board: Board::Hex3x2,
+            difficulty: None,
+            author: None,
+            pool: None,
+            constraints: GeneratorConstraints::default(),
+        };
+        let puzzles = generate(&options).unwrap();
+        assert_eq!(puzzles[0].board, "hex 3x2");
+        assert_eq!(puzzles[0].cells.len(), 6);
+    }
+
+    #[test]
+    fn too_large_to_rate() {
+        let options = GenerateOptions {
+            count: 1,
+            seed: Some(1),
+            board: Board::Grid3x3,
+            difficulty: Some(DifficultyBand::Easy),
+            author: None,
+            pool: None,
+            constraints: GeneratorConstraints::default(),
+        };
+        assert_eq!(generate(&options), Err(String::from("puzzles on the grid 3x3 board are too large to be rated")));
+    }
+}
@@ -0,0 +1,115 @@
+use std::io::Read;
+
+use puzzle_core::notation::Notation;
+use puzzle_core::pack::{Pack, PACK_VERSION};
+
+use crate::board::Board;
+
+/// Reads puzzles from the file at `path`, or from stdin if `path` is `-`.
+pub fn read_puzzles(path: &str) -> Result<Vec<Notation>, String> {
+    let text = if path == "-" {
+        let mut text = String::new();
+        std::io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|e| format!("failed to read stdin: {e}"))?;
+        text
+    } else {
+        std::fs::read_to_string(path).map_err(|e| format!("failed to read {path}: {e}"))?
+    };
+    parse_puzzles(&text)
+}
+
+/// Parses a puzzle written in the notation, a JSON object of a puzzle,
+/// a JSON array of puzzles as printed by `generate --json`, or a pack printed by `pack`.
+pub fn parse_puzzles(text: &str) -> Result<Vec<Notation>, String> {
+    let text = text.trim_start();
+    let json_array = text
+        .strip_prefix('[')
+        .is_some_and(|rest| rest.trim_start().starts_with(['{', ']']));
+    if json_array {
+        return serde_json::from_str(text).map_err(|e| format!("invalid JSON: {e}"));
+    }
+    if text.starts_with('{') {
+        let value = serde_json::from_str::<serde_json::Value>(text).map_err(|e| format!("invalid JSON: {e}"))?;
+        if value.get("stages").is_some() {
+            return parse_pack(value).map(|pack| pack.stages);
+        }
+    }
+    parse_puzzle(text).map(|puzzle| vec![puzzle])
+}
+
+/// Reads a pack, rejecting versions other than [`PACK_VERSION`] before looking at the rest.
+fn parse_pack(value: serde_json::Value) -> Result<Pack, String> {
+    match value.get("version").and_then(serde_json::Value::as_u64) {
+        Some(version) if version == u64::from(PACK_VERSION) => {
+            serde_json::from_value(value).map_err(|e| format!("invalid pack: {e}"))
+        }
+        Some(version) => Err(format!("unsupported pack version {version}; expected {PACK_VERSION}")),
+        None => Err(String::from("invalid pack: missing version")),
+    }
+}
+
+/// Parses a puzzle written either in the notation or as a JSON object.
+pub fn parse_puzzle(text: &str) -> Result<Notation, String> {
+    if text.trim_start().starts_with('{') {
+        serde_json::from_str(text).map_err(|e| format!("invalid JSON: {e}"))
+    } else {
+        text.parse().map_err(|e| format!("invalid notation: {e}"))
+    }
+}
+
+pub fn board_of(notation: &Notation) -> Result<Board, String> {
+    Board::from_name(&notation.board).ok_or_else(|| format!("unsupported board `{}`", notation.board))
+}
+
+#[cfg(test)]
+mod tests {
+    use puzzle_core::notation::Notation;
+    use puzzle_core::pack::{Pack, PACK_VERSION};
+
+    use crate::input::{parse_puzzle, parse_puzzles};
+
+    const PUZZLE: &str = "[Board \"diamond\"]\n[Cells \"1 2 3 4\"]\n[Goal \"10\"]\n";
+
+    #[test]
+    fn text_and_json() {
+        let notation = parse_puzzle(PUZZLE).unwrap();
+        let json = serde_json::to_string(&notation).unwrap();
+        assert_eq!(parse_puzzle(&json).unwrap(), notation);
+    }
+
+    #[test]
+    fn json_array() {
+        let notation = parse_puzzle(PUZZLE).unwrap();
+        let json = serde_json::to_string(&[&notation, &notation]).unwrap();
+        assert_eq!(parse_puzzles(&json).unwrap(), [notation.clone(), notation.clone()]);
+        assert_eq!(parse_puzzles(PUZZLE).unwrap(), [notation]);
+        assert_eq!(parse_puzzles(" [ ]").unwrap(), []);
+    }
+
+    #[test]
+    fn pack() {
+        let notation = parse_puzzle(PUZZLE).unwrap();
+        let mut pack = Pack {
+            version: PACK_VERSION,
+            board: notation.board.clone(),
+            seed: 1,
+            stages: vec![notation.clone()],
+        };
+        assert_eq!(parse_puzzles(&serde_json::to_string(&pack).unwrap()).unwrap(), [notation]);
+        pack.version += 1;
+        assert_eq!(
+            parse_puzzles(&serde_json::to_string(&pack).unwrap()),
+            Err(format!("unsupported pack version {}; expected {PACK_VERSION}", PACK_VERSION + 1))
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            parse_puzzle("[Board \"diamond\"]\n0 X"),
+            Err(format!("invalid notation: {}", "[Board \"diamond\"]\n0 X".parse::<Notation>().unwrap_err()))
+        );
+        assert!(parse_puzzle("{").unwrap_err().starts_with("invalid JSON"));
+    }
+}
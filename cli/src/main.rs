@@ -0,0 +1,248 @@
+//! A terminal tool to generate, solve, validate, draw and play puzzles without launching the game.
+//!
+//! Puzzles are read and written in the notation of [`puzzle_core::notation`], or in JSON.
+
+use std::num::NonZeroUsize;
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use puzzle_core::difficulty::RATE_STATE_LIMIT;
+use puzzle_core::answer::random_seed;
+use puzzle_core::notation::{format_moves, Notation};
+use puzzle_core::pack::{DifficultyMix, generate_pack, PackOptions};
+
+use crate::board::{Board, with_board};
+use crate::generate::{ConstraintArgs, DifficultyArg, generate, GenerateOptions, PoolArgs};
+use crate::input::read_puzzles;
+use crate::play::play;
+use crate::show::show;
+use crate::solve::solve;
+use crate::validate::validate;
+
+mod board;
+mod generate;
+mod input;
+mod play;
+mod show;
+mod solve;
+mod validate;
+
+#[derive(Parser)]
+#[command(version, about = "Generates, solves and validates make the hero puzzles")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generates puzzles together with their answers.
+    Generate {
+        /// The number of puzzles.
+        #[arg(short, long, default_value_t = 1)]
+        count: usize,
+        /// The seed of the first puzzle; random if omitted.
+        #[arg(short, long)]
+        seed: Option<u64>,
+        #[arg(short, long, value_enum, default_value_t = Board::Diamond)]
+        board: Board,
+        /// Only keep puzzles of this difficulty.
+        #[arg(short, long, value_enum)]
+        difficulty: Option<DifficultyArg>,
+        #[arg(long)]
+        author: Option<String>,
+        #[command(flatten)]
+        pool: PoolArgs,
+        #[command(flatten)]
+        constraints: ConstraintArgs,
+        /// Prints a JSON array instead of the notation.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Prints the shortest solution of a puzzle, or all of them.
+    Solve {
+        /// A puzzle file in the notation or in JSON; `-` reads stdin.
+        input: String,
+        #[arg(long)]
+        all: bool,
+        /// With `--all`, prints only one of the solutions that are the same written differently.
+        #[arg(long)]
+        distinct: bool,
+        /// The number of states searched with `--all` before giving up.
+        #[arg(long, default_value_t = RATE_STATE_LIMIT)]
+        max_states: usize,
+        /// Prints a JSON array of solutions instead of move lists.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Checks that the steps of a puzzle reach its goal.
+    Validate {
+        /// A puzzle file in the notation or in JSON; `-` reads stdin.
+        input: String,
+    },
+    /// Plays the game on the diamond board in the terminal.
+    Play {
+        /// The seed of the first stage; random if omitted.
+        #[arg(short, long)]
+        seed: Option<u64>,
+    },
+    /// Generates a pack of distinct puzzles as JSON.
+    ///
+    /// Mirror images on the board count as duplicates, and the puzzles are sorted from the easiest.
+    Pack {
+        /// The seed of the first candidate; random if omitted.
+        #[arg(short, long)]
+        seed: Option<u64>,
+        #[arg(short, long, value_enum, default_value_t = Board::Diamond)]
+        board: Board,
+        /// The number of easy puzzles.
+        #[arg(long, default_value_t = 0)]
+        easy: usize,
+        /// The number of medium puzzles.
+        #[arg(long, default_value_t = 0)]
+        medium: usize,
+        /// The number of hard puzzles.
+        #[arg(long, default_value_t = 0)]
+        hard: usize,
+        /// The number of puzzles of any difficulty.
+        #[arg(long, default_value_t = 0)]
+        any: usize,
+        #[command(flatten)]
+        pool: PoolArgs,
+        #[command(flatten)]
+        constraints: ConstraintArgs,
+        /// The number of seeds tried before giving up; a thousand per puzzle if omitted.
+        #[arg(long)]
+        max_seeds: Option<u64>,
+        /// The number of threads; as many as are available if omitted.
+        #[arg(long)]
+        threads: Option<NonZeroUsize>,
+    },
+    /// Draws the board of a puzzle.
+    Show {
+        /// A puzzle file in the notation or in JSON; `-` reads stdin.
+        input: String,
+        /// Draws the board after this many of the puzzle's steps.
+        #[arg(long, default_value_t = 0)]
+        moves: usize,
+    },
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse().command) {
+        Ok(output) => {
+            print!("{output}");
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command) -> Result<String, String> {
+    match command {
+        Command::Generate { count, seed, board, difficulty, author, pool, constraints, json } => {
+            let puzzles = generate(&GenerateOptions {
+                count,
+                seed,
+                board,
+                difficulty: difficulty.map(Into::into),
+                author,
+                pool: pool.to_pool().map_err(|e| e.to_string())?,
+                constraints: constraints.into(),
+            })?;
+            if json {
+                to_json(&puzzles)
+            } else {
+                Ok(puzzles
+                    .iter()
+                    .map(Notation::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n"))
+            }
+        }
+        Command::Solve { input, all, distinct, max_states, json } => {
+            for_each_puzzle(&input, |puzzle| {
+                let solutions = solve(puzzle, all, distinct, max_states)?;
+                if json {
+                    to_json(&solutions)
+                } else {
+                    Ok(solutions
+                        .iter()
+                        .map(|steps| format!("{}\n", format_moves(steps)))
+                        .collect())
+                }
+            })
+        }
+        Command::Validate { input } => {
+            for_each_puzzle(&input, |puzzle| validate(puzzle).map(|summary| format!("{summary}\n")))
+        }
+        Command::Play { seed } => {
+            play(seed).map(|_| String::new()).map_err(|e| e.to_string())
+        }
+        Command::Pack { seed, board, easy, medium, hard, any, pool, constraints, max_seeds, threads } => {
+            let mix = DifficultyMix { easy, medium, hard, any };
+            if mix.total() == 0 {
+                return Err(String::from("the pack is empty; pass --easy, --medium, --hard or --any"));
+            }
+            let mut options = PackOptions::new(seed.unwrap_or_else(random_seed), mix);
+            options.pool = pool.to_pool().map_err(|e| e.to_string())?;
+            options.constraints = constraints.into();
+            if let Some(max_seeds) = max_seeds {
+                options.max_seeds = max_seeds;
+            }
+            if let Some(threads) = threads {
+                options.threads = threads;
+            }
+            let pack = with_board!(board, generate_pack(&options)).map_err(|e| e.to_string())?;
+            to_json(&pack)
+        }
+        Command::Show { input, moves } => {
+            for_each_puzzle(&input, |puzzle| show(puzzle, moves))
+        }
+    }
+}
+
+/// Runs `f` on every puzzle in `input` and concatenates the outputs.
+///
+/// Every puzzle is processed even if some fail, and all errors are reported;
+/// outputs and errors are numbered by the puzzle when the input has more than one.
+fn for_each_puzzle(input: &str, f: impl Fn(&Notation) -> Result<String, String>) -> Result<String, String> {
+    let puzzles = read_puzzles(input)?;
+    if let [puzzle] = puzzles.as_slice() {
+        return f(puzzle);
+    }
+    let mut output = String::new();
+    let mut errors = Vec::new();
+    for (i, puzzle) in puzzles.iter().enumerate() {
+        match f(puzzle) {
+            Ok(o) => output.push_str(&format!("puzzle {}:\n{o}", i + 1)),
+            Err(e) => errors.push(format!("puzzle {}: {e}", i + 1)),
+        }
+    }
+    if errors.is_empty() {
+        Ok(output)
+    } else {
+        Err(errors.join("\n"))
+    }
+}
+
+fn to_json(value: &impl serde::Serialize) -> Result<String, String> {
+    serde_json::to_string_pretty(value)
+        .map(|json| format!("{json}\n"))
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use crate::Cli;
+
+    #[test]
+    fn verify_cli() {
+        Cli::command().debug_assert();
+    }
+}
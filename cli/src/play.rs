@@ -0,0 +1,196 @@
+//! Plays the game in a terminal, drawn as text.
+//!
+//! Only the terminal is needed, so this also works over SSH and on machines without a GPU.
+
+use std::io::{stdout, Write};
+use std::time::Duration;
+
+use crossterm::{cursor, execute, queue, terminal};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::Print;
+use puzzle_core::answer::random_seed;
+use puzzle_core::move_dir::MoveDir;
+
+use crate::play::game::{Game, Input, Mode};
+use crate::show::render_labels;
+
+pub mod game;
+
+/// The interval between the steps of a played answer.
+const ANSWER_STEP_INTERVAL: Duration = Duration::from_millis(600);
+
+const HELP: &str = "arrows: select  enter: choose  esc: cancel  1-9: direction (numpad layout)
+R: retry  G: new stage  P: play answer  Z: undo  X: redo  Q: quit";
+
+/// Runs the game until the player quits.
+pub fn play(seed: Option<u64>) -> std::io::Result<()> {
+    let mut game = Game::new(seed.unwrap_or_else(random_seed));
+    let mut stdout = stdout();
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let result = run(&mut game, &mut stdout);
+
+    execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    result
+}
+
+fn run(game: &mut Game, stdout: &mut impl Write) -> std::io::Result<()> {
+    loop {
+        queue!(stdout, terminal::Clear(terminal::ClearType::All), cursor::MoveTo(0, 0))?;
+        for line in draw(game) {
+            // Raw mode doesn't return the carriage on a line feed.
+            queue!(stdout, Print(line), Print("\r\n"))?;
+        }
+        stdout.flush()?;
+
+        if matches!(game.mode(), Mode::PlayAnswer { .. }) && !event::poll(ANSWER_STEP_INTERVAL)? {
+            game.tick();
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        if is_quit(&key) {
+            return Ok(());
+        }
+        if let Some(input) = to_input(&key) {
+            game.handle(input, random_seed);
+        }
+    }
+}
+
+fn is_quit(key: &KeyEvent) -> bool {
+    matches!(key.code, KeyCode::Char('q') | KeyCode::Char('Q'))
+        || (key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL))
+}
+
+fn to_input(key: &KeyEvent) -> Option<Input> {
+    let input = match key.code {
+        KeyCode::Up => Input::Arrow(MoveDir::Up),
+        KeyCode::Down => Input::Arrow(MoveDir::Down),
+        KeyCode::Left => Input::Arrow(MoveDir::Left),
+        KeyCode::Right => Input::Arrow(MoveDir::Right),
+        KeyCode::Enter | KeyCode::Char(' ') => Input::Confirm,
+        KeyCode::Esc | KeyCode::Backspace => Input::Cancel,
+        KeyCode::Char(c) => match c.to_ascii_lowercase() {
+            'r' => Input::Retry,
+            'g' => Input::Generate,
+            'p' => Input::PlayAnswer,
+            'z' => Input::Undo,
+            'x' => Input::Redo,
+            '7' => Input::Dir(MoveDir::LeftUp),
+            '8' => Input::Dir(MoveDir::Up),
+            '9' => Input::Dir(MoveDir::RightUp),
+            '4' => Input::Dir(MoveDir::Left),
+            '6' => Input::Dir(MoveDir::Right),
+            '1' => Input::Dir(MoveDir::LeftDown),
+            '2' => Input::Dir(MoveDir::Down),
+            '3' => Input::Dir(MoveDir::RightDown),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(input)
+}
+
+/// Returns the lines of the screen.
+///
+/// The selected cell is marked with `>` and `<`, and moved cells are drawn in parentheses.
+pub fn draw(game: &Game) -> Vec<String> {
+    let stage = game.stage();
+    let labels = stage
+        .movable_ratios()
+        .iter()
+        .enumerate()
+        .map(|(i, cell)| {
+            let label = match cell {
+                Some(cell) if cell.moved => format!("({i}:{})", cell.ratio),
+                Some(cell) => format!("[{i}:{}]", cell.ratio),
+                None => format!("[{i}: ]"),
+            };
+            if i == game.selected() {
+                format!(">{label}<")
+            } else {
+                format!(" {label} ")
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut lines = vec![
+        format!("make the hero  seed: {}  cleared: {}", game.seed(), game.cleared()),
+        String::new(),
+    ];
+    lines.extend(render_labels(stage.topology(), &labels).lines().map(String::from));
+    lines.push(String::new());
+    lines.push(format!("goal: {}", game.goal()));
+    lines.push(status(game));
+    lines.push(String::new());
+    lines.extend(HELP.lines().map(String::from));
+    lines
+}
+
+fn status(game: &Game) -> String {
+    match game.mode() {
+        Mode::Cleared => String::from("Clear!  [G]: next stage"),
+        Mode::PlayAnswer { .. } => String::from("Playing the answer..."),
+        _ if !game.is_reachable() => String::from("No way to reach the goal  [R]: retry  [Z]: undo"),
+        Mode::SelectCell => String::from("Select a cell"),
+        Mode::SelectDir { dirs, index } => {
+            let dirs = dirs
+                .iter()
+                .enumerate()
+                .map(|(i, dir)| {
+                    let operation = game.stage().operation(game.selected(), *dir).map(|op| op.symbol()).unwrap_or("");
+                    let dir = format!("{} {operation}", dir.abbreviation());
+                    if i == *index {
+                        format!("[{dir}]")
+                    } else {
+                        format!(" {dir} ")
+                    }
+                })
+                .collect::<String>();
+            format!("Direction:{dirs}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use puzzle_core::move_dir::MoveDir;
+
+    use crate::play::draw;
+    use crate::play::game::{Game, Input};
+
+    #[test]
+    fn draw_board() {
+        let game = Game::new(42);
+        let lines = draw(&game);
+        assert_eq!(lines[0], "make the hero  seed: 42  cleared: 0");
+        assert!(lines.iter().any(|l| l.contains(">[0:1]<")));
+        assert!(lines.iter().any(|l| l.contains(" [3:4] ")));
+        assert!(lines.contains(&String::from("goal: 4/5")));
+        assert!(lines.contains(&String::from("Select a cell")));
+    }
+
+    #[test]
+    fn draw_direction_choice() {
+        let mut game = Game::new(42);
+        game.handle(Input::Confirm, || 0);
+        let lines = draw(&game);
+        assert!(lines.iter().any(|l| l.starts_with("Direction:[RU -]")));
+    }
+
+    #[test]
+    fn draw_unreachable() {
+        let mut game = Game::new(42);
+        // [1, 3, 9, 4] -> [_, 2, 9, 4] can't lead to 4/5
+        game.handle(Input::Dir(MoveDir::RightUp), || 0);
+        assert!(!game.is_reachable());
+        assert!(draw(&game).iter().any(|l| l.starts_with("No way to reach the goal")));
+    }
+}
@@ -0,0 +1,317 @@
+//! The state of a game in the terminal, independent of how it is drawn.
+
+use puzzle_core::answer::generate_stage_from_seed;
+use puzzle_core::answer::steps::Steps;
+use puzzle_core::calculator::small_size::SmallSizeCalculator;
+use puzzle_core::move_dir::MoveDir;
+use puzzle_core::ratio::Ratio;
+use puzzle_core::stage::{RatioArray, Stage};
+use puzzle_core::topology::Topology;
+
+pub type DiamondStage = Stage<4, SmallSizeCalculator>;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Mode {
+    /// Choosing a cell.
+    SelectCell,
+    /// Choosing one of `dirs` for the selected cell; `index` is the highlighted one.
+    SelectDir {
+        dirs: Vec<MoveDir>,
+        index: usize,
+    },
+    /// Playing the answer one step per [`Game::tick`]; `next` is the index of the next step.
+    PlayAnswer {
+        next: usize,
+    },
+    /// Only the goal is left; waiting for the next stage.
+    Cleared,
+}
+
+/// What the player asked for; the terminal keys are mapped to these.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Input {
+    /// An arrow key: moves the selection, or cycles the directions.
+    Arrow(MoveDir),
+    /// Picks a direction directly.
+    Dir(MoveDir),
+    Confirm,
+    Cancel,
+    Retry,
+    Generate,
+    PlayAnswer,
+    Undo,
+    Redo,
+}
+
+/// A game on the diamond board with the same controls as the bevy app.
+#[derive(Debug, Clone)]
+pub struct Game {
+    seed: u64,
+    ratios: RatioArray<4>,
+    goal: Ratio,
+    answer: Steps,
+    stage: DiamondStage,
+    selected: usize,
+    mode: Mode,
+    cleared: usize,
+}
+
+impl Game {
+    pub fn new(seed: u64) -> Self {
+        let (ratios, answer) = generate_stage_from_seed::<4, SmallSizeCalculator>(seed)
+            .expect("integers on the diamond board always have an answer");
+        Self {
+            seed,
+            ratios,
+            goal: answer.ratio,
+            answer: answer.steps,
+            stage: Stage::from(ratios),
+            selected: 0,
+            mode: Mode::SelectCell,
+            cleared: 0,
+        }
+    }
+
+    #[inline]
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    #[inline]
+    pub const fn goal(&self) -> Ratio {
+        self.goal
+    }
+
+    #[inline]
+    pub const fn stage(&self) -> &DiamondStage {
+        &self.stage
+    }
+
+    #[inline]
+    pub const fn selected(&self) -> usize {
+        self.selected
+    }
+
+    #[inline]
+    pub const fn mode(&self) -> &Mode {
+        &self.mode
+    }
+
+    /// Returns the number of stages the player has cleared, not counting played answers.
+    #[inline]
+    pub const fn cleared(&self) -> usize {
+        self.cleared
+    }
+
+    /// Returns whether the goal can still be reached.
+    pub fn is_reachable(&self) -> bool {
+        self.mode == Mode::Cleared || self.stage.is_reachable(self.goal)
+    }
+
+    /// Replaces the stage with the one generated from `seed`, keeping the clear count.
+    pub fn generate(&mut self, seed: u64) {
+        let cleared = self.cleared;
+        *self = Self::new(seed);
+        self.cleared = cleared;
+    }
+
+    /// Handles `input`; `new_seed` is called only when a new stage is generated.
+    pub fn handle(&mut self, input: Input, new_seed: impl FnOnce() -> u64) {
+        match (&self.mode, input) {
+            (_, Input::Generate) => self.generate(new_seed()),
+            (Mode::Cleared, _) => {}
+            (_, Input::Retry) => self.retry(),
+            (_, Input::PlayAnswer) => {
+                self.retry();
+                self.mode = Mode::PlayAnswer { next: 0 };
+            }
+            (Mode::PlayAnswer { .. }, _) => {}
+            (_, Input::Undo) => {
+                self.stage.undo();
+                self.mode = Mode::SelectCell;
+            }
+            (_, Input::Redo) => {
+                self.stage.redo();
+                self.mode = Mode::SelectCell;
+            }
+            (Mode::SelectCell, Input::Arrow(dir)) => {
+                if let Some(cell_no) = self.nearest_cell(dir) {
+                    self.selected = cell_no;
+                }
+            }
+            (Mode::SelectCell, Input::Confirm) => {
+                let dirs = self.stage.movable_dirs(self.selected);
+                if !dirs.is_empty() {
+                    self.mode = Mode::SelectDir { dirs, index: 0 };
+                }
+            }
+            (Mode::SelectCell | Mode::SelectDir { .. }, Input::Dir(dir)) => self.move_selected(dir),
+            (Mode::SelectDir { dirs, index }, Input::Arrow(arrow)) => {
+                let index = match arrow {
+                    MoveDir::Left | MoveDir::Up => (index + dirs.len() - 1) % dirs.len(),
+                    _ => (index + 1) % dirs.len(),
+                };
+                self.mode = Mode::SelectDir { dirs: dirs.clone(), index };
+            }
+            (Mode::SelectDir { dirs, index }, Input::Confirm) => self.move_selected(dirs[*index]),
+            (Mode::SelectDir { .. }, Input::Cancel) => self.mode = Mode::SelectCell,
+            _ => {}
+        }
+    }
+
+    /// Advances the answer being played by one step.
+    pub fn tick(&mut self) {
+        let Mode::PlayAnswer { next } = &mut self.mode else {
+            return;
+        };
+        match self.answer.get(*next).copied() {
+            Some((cell_no, dir)) => {
+                *next += 1;
+                self.selected = cell_no;
+                if self.stage.move_cell(cell_no, dir).is_err() {
+                    self.mode = Mode::SelectCell;
+                }
+            }
+            None => self.mode = Mode::SelectCell,
+        }
+        if self.stage.last_ratio() == Some(self.goal) {
+            self.mode = Mode::Cleared;
+        }
+    }
+
+    fn retry(&mut self) {
+        self.stage = Stage::from(self.ratios);
+        self.mode = Mode::SelectCell;
+    }
+
+    fn move_selected(&mut self, dir: MoveDir) {
+        let Ok(outcome) = self.stage.move_cell(self.selected, dir) else {
+            return;
+        };
+        self.selected = outcome.cells().1;
+        self.mode = if self.stage.last_ratio() == Some(self.goal) {
+            self.cleared += 1;
+            Mode::Cleared
+        } else {
+            Mode::SelectCell
+        };
+    }
+
+    /// Returns the cell closest to the selected one in the direction of an arrow key.
+    fn nearest_cell(&self, arrow: MoveDir) -> Option<usize> {
+        let (dx, dy) = match arrow {
+            MoveDir::Up => (0., 1.),
+            MoveDir::Down => (0., -1.),
+            MoveDir::Left => (-1., 0.),
+            MoveDir::Right => (1., 0.),
+            _ => return None,
+        };
+        let topology = self.stage.topology();
+        let from = topology.position(self.selected);
+        (0..topology.cell_count())
+            .filter_map(|cell_no| {
+                let to = topology.position(cell_no);
+                let (x, y) = (to.x - from.x, to.y - from.y);
+                let along = x * dx + y * dy;
+                let across = (x * dy - y * dx).abs();
+                (0. < along).then_some((cell_no, along + across * 2.))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(cell_no, _)| cell_no)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use puzzle_core::move_dir::MoveDir;
+
+    use crate::play::game::{Game, Input, Mode};
+
+    #[test]
+    fn select_with_arrows() {
+        let mut game = Game::new(42);
+        assert_eq!(game.selected(), 0);
+        game.handle(Input::Arrow(MoveDir::Up), || 0);
+        assert_eq!(game.selected(), 1);
+        game.handle(Input::Arrow(MoveDir::Right), || 0);
+        assert_eq!(game.selected(), 3);
+        game.handle(Input::Arrow(MoveDir::Right), || 0);
+        assert_eq!(game.selected(), 3);
+        game.handle(Input::Arrow(MoveDir::Left), || 0);
+        assert_eq!(game.selected(), 0);
+    }
+
+    #[test]
+    fn pick_direction() {
+        let mut game = Game::new(42);
+        game.handle(Input::Confirm, || 0);
+        let Mode::SelectDir { dirs, index: 0 } = game.mode().clone() else {
+            panic!("must be choosing a direction");
+        };
+        game.handle(Input::Arrow(MoveDir::Right), || 0);
+        assert_eq!(game.mode(), &Mode::SelectDir { dirs: dirs.clone(), index: 1 % dirs.len() });
+        game.handle(Input::Cancel, || 0);
+        assert_eq!(game.mode(), &Mode::SelectCell);
+
+        let initial = game.stage().ratios();
+        game.handle(Input::Confirm, || 0);
+        game.handle(Input::Confirm, || 0);
+        assert_ne!(game.stage().ratios(), initial);
+        assert_eq!(game.mode(), &Mode::SelectCell);
+    }
+
+    #[test]
+    fn undo_redo_retry() {
+        let mut game = Game::new(42);
+        let initial = game.stage().ratios();
+        game.handle(Input::Dir(MoveDir::Right), || 0);
+        assert_ne!(game.stage().ratios(), initial);
+        let moved = game.stage().ratios();
+        game.handle(Input::Undo, || 0);
+        assert_eq!(game.stage().ratios(), initial);
+        game.handle(Input::Redo, || 0);
+        assert_eq!(game.stage().ratios(), moved);
+        game.handle(Input::Retry, || 0);
+        assert_eq!(game.stage().ratios(), initial);
+    }
+
+    #[test]
+    fn play_answer() {
+        let mut game = Game::new(42);
+        game.handle(Input::PlayAnswer, || 0);
+        for _ in 0..100 {
+            game.tick();
+        }
+        assert_eq!(game.mode(), &Mode::Cleared);
+        assert_eq!(game.stage().last_ratio(), Some(game.goal()));
+        assert_eq!(game.cleared(), 0);
+    }
+
+    #[test]
+    fn clear_by_hand_then_generate() {
+        let mut game = Game::new(42);
+        // The answer of seed 42: 2 U; 0 R; 0 R; 2 LU; 0 RU; 1 RD
+        for (cell_no, dir) in [(2, MoveDir::Up), (0, MoveDir::Right), (0, MoveDir::Right), (2, MoveDir::LeftUp), (0, MoveDir::RightUp), (1, MoveDir::RightDown)] {
+            while game.selected() != cell_no {
+                let arrow = match (game.selected(), cell_no) {
+                    (_, 0) => MoveDir::Left,
+                    (_, 1) => MoveDir::Up,
+                    (_, 2) => MoveDir::Down,
+                    _ => MoveDir::Right,
+                };
+                game.handle(Input::Arrow(arrow), || 0);
+            }
+            game.handle(Input::Dir(dir), || 0);
+        }
+        assert_eq!(game.mode(), &Mode::Cleared);
+        assert_eq!(game.cleared(), 1);
+        assert!(game.is_reachable());
+
+        game.handle(Input::Undo, || 0);
+        assert_eq!(game.mode(), &Mode::Cleared);
+        game.handle(Input::Generate, || 7);
+        assert_eq!(game.seed(), 7);
+        assert_eq!(game.mode(), &Mode::SelectCell);
+        assert_eq!(game.cleared(), 1);
+    }
+}
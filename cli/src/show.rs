@@ -0,0 +1,106 @@
+use puzzle_core::calculator::Calculator;
+use puzzle_core::notation::Notation;
+use puzzle_core::ratio::Ratio;
+use puzzle_core::topology::Topology;
+
+use crate::board::with_board;
+use crate::input::board_of;
+
+/// Draws the board after the first `moves` steps of the puzzle, followed by the goal.
+pub fn show(notation: &Notation, moves: usize) -> Result<String, String> {
+    with_board!(board_of(notation)?, show_on(notation, moves))
+}
+
+fn show_on<const STAGE_SIZE: usize, Calc>(notation: &Notation, moves: usize) -> Result<String, String>
+    where Calc: Calculator + Default + 'static
+{
+    let mut stage = notation.to_stage::<STAGE_SIZE, Calc>(Calc::default()).map_err(|e| e.to_string())?;
+    for (i, (cell_no, dir)) in notation.steps.iter().take(moves).enumerate() {
+        stage.move_cell(*cell_no, *dir).map_err(|e| format!("move {} `{cell_no} {}` is not allowed: {e}", i + 1, dir.abbreviation()))?;
+    }
+    Ok(format!("{}\ngoal: {}\n", render(stage.topology(), &stage.ratios()), notation.goal))
+}
+
+/// Draws each cell as `[cell_no:value]` at its position on the board.
+pub fn render(topology: &impl Topology, cells: &[Option<Ratio>]) -> String {
+    let labels = cells
+        .iter()
+        .enumerate()
+        .map(|(i, cell)| match cell {
+            Some(ratio) => format!("[{i}:{ratio}]"),
+            None => format!("[{i}: ]"),
+        })
+        .collect::<Vec<_>>();
+    render_labels(topology, &labels)
+}
+
+/// Draws `labels[i]` at the position of cell `i`.
+///
+/// One cell unit is as wide as the widest label and two lines high.
+pub fn render_labels(topology: &impl Topology, labels: &[String]) -> String {
+    let width = labels.iter().map(|l| l.chars().count()).max().unwrap_or(0) + 1;
+    let positions = (0..labels.len()).map(|i| topology.position(i)).collect::<Vec<_>>();
+    let min_x = positions.iter().map(|p| p.x).fold(f32::INFINITY, f32::min);
+    let max_y = positions.iter().map(|p| p.y).fold(f32::NEG_INFINITY, f32::max);
+
+    let mut lines: Vec<Vec<char>> = Vec::new();
+    for (label, pos) in labels.iter().zip(positions) {
+        let row = ((max_y - pos.y) * 2.).round() as usize;
+        let column = ((pos.x - min_x) * width as f32).round() as usize;
+        if lines.len() <= row {
+            lines.resize(row + 1, Vec::new());
+        }
+        let line = &mut lines[row];
+        let end = column + label.chars().count();
+        if line.len() < end {
+            line.resize(end, ' ');
+        }
+        line.splice(column..end, label.chars());
+    }
+    lines
+        .into_iter()
+        .map(|line| line.into_iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use puzzle_core::ratio::Ratio;
+    use puzzle_core::topology::diamond::DiamondTopology;
+    use puzzle_core::topology::grid::GridTopology;
+
+    use crate::input::parse_puzzle;
+    use crate::show::{render, show};
+
+    #[test]
+    fn diamond() {
+        let cells = [1, 2, 3, 4].map(|r| Some(Ratio::from(r)));
+        assert_eq!(render(&DiamondTopology, &cells), [
+            "      [1:2]",
+            "",
+            "[0:1]       [3:4]",
+            "",
+            "      [2:3]",
+        ].join("\n"));
+    }
+
+    #[test]
+    fn grid_with_empty_cell() {
+        let cells = [Some(Ratio::from(10)), None, Some(Ratio::from(3)), Some(Ratio::from(4))];
+        assert_eq!(render(&GridTopology::<2, 2>, &cells), [
+            "[0:10] [1: ]",
+            "",
+            "[2:3]  [3:4]",
+        ].join("\n"));
+    }
+
+    #[test]
+    fn after_moves() {
+        let puzzle = parse_puzzle("[Board \"diamond\"]\n[Cells \"1 2 3 4\"]\n[Goal \"10\"]\n2 LU").unwrap();
+        let board = show(&puzzle, 1).unwrap();
+        assert!(board.contains("[0:4]"));
+        assert!(board.contains("[2: ]"));
+        assert!(board.ends_with("goal: 10\n"));
+    }
+}
@@ -0,0 +1,61 @@
+use puzzle_core::answer::steps::Steps;
+use puzzle_core::calculator::Calculator;
+use puzzle_core::canonical;
+use puzzle_core::notation::Notation;
+use puzzle_core::solver::{explore_within, shortest};
+
+use crate::board::with_board;
+use crate::input::board_of;
+
+/// Returns the shortest solution of the puzzle, or all of them if `all` is set.
+///
+/// With `distinct`, only one of the solutions that are the same written differently is kept.
+/// Searching all solutions gives up after `max_states` states.
+pub fn solve(notation: &Notation, all: bool, distinct: bool, max_states: usize) -> Result<Vec<Steps>, String> {
+    with_board!(board_of(notation)?, solve_on(notation, all, distinct, max_states))
+}
+
+fn solve_on<const STAGE_SIZE: usize, Calc>(notation: &Notation, all: bool, distinct: bool, max_states: usize) -> Result<Vec<Steps>, String>
+    where Calc: Calculator + Default + Clone + 'static
+{
+    let stage = notation.to_stage::<STAGE_SIZE, Calc>(Calc::default()).map_err(|e| e.to_string())?;
+    let solutions = if all {
+        explore_within(&stage, notation.goal, max_states)
+            .ok_or_else(|| format!("gave up after {max_states} states"))?
+            .solutions
+    } else {
+        shortest(&stage, notation.goal).into_iter().collect()
+    };
+    if solutions.is_empty() {
+        return Err(format!("{} can't be reached", notation.goal));
+    }
+    if distinct {
+        return Ok(canonical::distinct(&stage, solutions));
+    }
+    Ok(solutions)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::parse_puzzle;
+    use crate::solve::solve;
+
+    #[test]
+    fn shortest_and_all() {
+        let puzzle = parse_puzzle("[Board \"diamond\"]\n[Cells \"1 2 3 4\"]\n[Goal \"10\"]\n").unwrap();
+        let shortest = solve(&puzzle, false, false, usize::MAX).unwrap();
+        assert_eq!(shortest.len(), 1);
+        let all = solve(&puzzle, true, false, usize::MAX).unwrap();
+        assert!(all.contains(&shortest[0]));
+        assert!(all.iter().all(|steps| shortest[0].len() <= steps.len()));
+        let distinct = solve(&puzzle, true, true, usize::MAX).unwrap();
+        assert!(distinct.len() < all.len());
+    }
+
+    #[test]
+    fn unreachable() {
+        let puzzle = parse_puzzle("[Board \"diamond\"]\n[Cells \"1 2 3 4\"]\n[Goal \"1000\"]\n").unwrap();
+        assert_eq!(solve(&puzzle, false, false, usize::MAX), Err(String::from("1000 can't be reached")));
+        assert_eq!(solve(&puzzle, true, false, 10), Err(String::from("gave up after 10 states")));
+    }
+}
@@ -0,0 +1,82 @@
+use puzzle_core::answer::steps::VerifyError;
+use puzzle_core::calculator::Calculator;
+use puzzle_core::notation::Notation;
+
+use crate::board::with_board;
+use crate::input::board_of;
+
+/// Checks that every step of the puzzle is allowed and that the steps end with only the goal left.
+///
+/// Returns a short summary on success.
+pub fn validate(notation: &Notation) -> Result<String, String> {
+    with_board!(board_of(notation)?, validate_on(notation))
+}
+
+fn validate_on<const STAGE_SIZE: usize, Calc>(notation: &Notation) -> Result<String, String>
+    where Calc: Calculator + Default + Clone + 'static
+{
+    let stage = notation.to_stage::<STAGE_SIZE, Calc>(Calc::default()).map_err(|e| e.to_string())?;
+    if notation.steps.is_empty() {
+        return Err(String::from("the puzzle has no steps"));
+    }
+    match notation.steps.verify(&stage, notation.goal) {
+        Ok(ratio) => Ok(format!("ok: {} moves reach {ratio}", notation.steps.len())),
+        Err(VerifyError::IllegalStep { index, error }) => {
+            let (cell_no, dir) = notation.steps[index];
+            Err(format!("move {} `{cell_no} {}` is not allowed: {error}", index + 1, dir.abbreviation()))
+        }
+        Err(VerifyError::WrongValue(ratio)) => Err(format!("the steps reach {ratio}, but the goal is {}", notation.goal)),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use puzzle_core::notation::Notation;
+    use puzzle_core::ratio::Ratio;
+
+    use crate::input::parse_puzzle;
+    use crate::solve::solve;
+    use crate::validate::validate;
+
+    const HEADERS: &str = "[Board \"diamond\"]\n[Cells \"1 2 3 4\"]\n";
+
+    #[test]
+    fn valid() {
+        let puzzle = solved(10);
+        assert_eq!(validate(&puzzle), Ok(format!("ok: {} moves reach 10", puzzle.steps.len())));
+    }
+
+    #[test]
+    fn wrong_goal() {
+        let mut puzzle = solved(10);
+        puzzle.goal = Ratio::from(11);
+        assert_eq!(validate(&puzzle), Err(String::from("the steps reach 10, but the goal is 11")));
+    }
+
+    #[test]
+    fn unfinished() {
+        let mut puzzle = solved(10);
+        puzzle.steps.pop_front();
+        assert!(validate(&puzzle).is_err());
+    }
+
+    #[test]
+    fn illegal_move() {
+        let puzzle = parse_puzzle(&format!("{HEADERS}[Goal \"10\"]\n2 LU; 2 LU")).unwrap();
+        assert_eq!(validate(&puzzle), Err(String::from("move 2 `2 LU` is not allowed: there is no cell to move")));
+    }
+
+    #[test]
+    fn no_steps() {
+        let puzzle = parse_puzzle(&format!("{HEADERS}[Goal \"10\"]\n")).unwrap();
+        assert_eq!(validate(&puzzle), Err(String::from("the puzzle has no steps")));
+    }
+
+    /// The puzzle with the shortest solution to `goal` as its steps.
+    fn solved(goal: isize) -> Notation {
+        let mut puzzle = parse_puzzle(&format!("{HEADERS}[Goal \"{goal}\"]\n")).unwrap();
+        puzzle.steps = solve(&puzzle, false, false, usize::MAX).unwrap().remove(0);
+        puzzle
+    }
+}
@@ -1,65 +1,380 @@
-use rand::prelude::{SliceRandom, ThreadRng};
+use std::fmt::{Display, Formatter};
 
+use rand::prelude::IndexedRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::answer::constraints::{ConstraintError, GeneratorConstraints};
+use crate::answer::pool::{NumberPool, PoolError};
 use crate::answer::steps::Steps;
 use crate::calculator::Calculator;
+use crate::difficulty::{Difficulty, DifficultyBand};
+use crate::number::PuzzleNumber;
 use crate::ratio::Ratio;
+use crate::stage::history::MoveKind;
 use crate::stage::{RatioArray, Stage};
 
+pub mod constraints;
+pub mod pool;
 pub mod steps;
 
-pub struct AnswerInfo {
-    pub ratio: Ratio,
+/// The number of answers [`AnswerInfo::generate_in_band`] tries before giving up.
+pub const BAND_ATTEMPTS: usize = 64;
+
+/// The number of random walks [`AnswerInfo::generate_on_stage`] tries before giving up.
+///
+/// Far more than [`DEFAULT_ATTEMPTS`](constraints::DEFAULT_ATTEMPTS),
+/// as only about one walk in 400 reaches a single cell on a 3x2 grid.
+pub const ANSWER_ATTEMPTS: usize = 1 << 16;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnswerInfo<Num = Ratio> {
+    pub ratio: Num,
     pub steps: Steps,
+    /// `None` if the board is too large to be rated; see [`Difficulty::rate`].
+    pub difficulty: Option<Difficulty>,
 }
 
-impl AnswerInfo {
-    pub fn generate<const STAGE_SIZE: usize, Calc: Calculator + Default + 'static>(ratios: RatioArray<STAGE_SIZE>) -> Self {
-        let mut stage = Stage::new(Calc::default(), ratios);
-        let mut rng = rand::thread_rng();
+impl<Num: PuzzleNumber> AnswerInfo<Num> {
+    pub fn generate<const STAGE_SIZE: usize, Calc: Calculator + Default + Clone + 'static>(ratios: RatioArray<STAGE_SIZE, Num>) -> Result<Self, ConstraintError> {
+        Self::generate_with_rng::<STAGE_SIZE, Calc>(ratios, &mut rand::thread_rng())
+    }
+
+    /// Generates an answer that is reproducible from `seed`.
+    #[inline]
+    pub fn generate_from_seed<const STAGE_SIZE: usize, Calc: Calculator + Default + Clone + 'static>(
+        ratios: RatioArray<STAGE_SIZE, Num>,
+        seed: u64,
+    ) -> Result<Self, ConstraintError> {
+        Self::generate_with_rng::<STAGE_SIZE, Calc>(ratios, &mut seeded_rng(seed))
+    }
+
+    pub fn generate_with_rng<const STAGE_SIZE: usize, Calc: Calculator + Default + Clone + 'static>(
+        ratios: RatioArray<STAGE_SIZE, Num>,
+        rng: &mut impl Rng,
+    ) -> Result<Self, ConstraintError> {
+        Self::generate_on_stage(&Stage::new(Calc::default(), ratios), rng)
+    }
+
+    /// Generates an answer from the current state of `stage`, played with its rule set.
+    ///
+    /// A stage may have no answer at all, for example if every combine overflows,
+    /// so this gives up once [`ANSWER_ATTEMPTS`] random walks got stuck,
+    /// like [`AnswerInfo::generate_constrained`] with no other constraint.
+    #[inline]
+    pub fn generate_on_stage<const STAGE_SIZE: usize, Calc: Calculator + Clone + 'static>(
+        stage: &Stage<STAGE_SIZE, Calc, Num>,
+        rng: &mut impl Rng,
+    ) -> Result<Self, ConstraintError> {
+        let constraints = GeneratorConstraints {
+            max_attempts: ANSWER_ATTEMPTS,
+            ..Default::default()
+        };
+        Self::generate_constrained(stage, &constraints, rng)
+    }
+
+    /// Generates an answer whose difficulty falls into `band`.
+    ///
+    /// Returns `None` if no such answer was found within [`BAND_ATTEMPTS`] tries;
+    /// the ratios may simply not allow the band, so try other ratios in that case.
+    /// Answers that can't be rated never match, and it stops early if the ratios have no answer at all.
+    pub fn generate_in_band<const STAGE_SIZE: usize, Calc: Calculator + Default + Clone + 'static>(
+        ratios: RatioArray<STAGE_SIZE, Num>,
+        band: DifficultyBand,
+    ) -> Option<Self> {
+        Self::generate_in_band_with_rng::<STAGE_SIZE, Calc>(ratios, band, &mut rand::thread_rng())
+    }
+
+    pub fn generate_in_band_with_rng<const STAGE_SIZE: usize, Calc: Calculator + Default + Clone + 'static>(
+        ratios: RatioArray<STAGE_SIZE, Num>,
+        band: DifficultyBand,
+        rng: &mut impl Rng,
+    ) -> Option<Self> {
+        (0..BAND_ATTEMPTS)
+            .map_while(|_| Self::generate_with_rng::<STAGE_SIZE, Calc>(ratios.clone(), rng).ok())
+            .find(|answer| answer.difficulty.as_ref().is_some_and(|d| d.band() == band))
+    }
 
-        loop {
-            if let Some(answer_info) = try_generate(&mut stage, &mut rng) {
-                return answer_info;
-            } else {
-                stage = Stage::new(Calc::default(), ratios);
+    /// Generates an answer from the current state of `stage` that meets `constraints`.
+    ///
+    /// Tries up to [`GeneratorConstraints::max_attempts`] random walks, counting the ones that get stuck,
+    /// and fails if none of them meets the constraints.
+    /// With the default constraints, this picks the same answer as [`AnswerInfo::generate_on_stage`],
+    /// which only tries more walks.
+    pub fn generate_constrained<const STAGE_SIZE: usize, Calc: Calculator + Clone + 'static>(
+        stage: &Stage<STAGE_SIZE, Calc, Num>,
+        constraints: &GeneratorConstraints<Num>,
+        rng: &mut impl Rng,
+    ) -> Result<Self, ConstraintError> {
+        let initial = stage.ratios();
+        constraints.check(initial.iter().flatten().count())?;
+        for _ in 0..constraints.max_attempts {
+            let mut walk = stage.clone();
+            let Some((ratio, steps)) = try_generate(&mut walk, rng) else {
+                continue;
+            };
+            let combines = walk.history()[stage.position()..]
+                .iter()
+                .filter(|record| record.kind == MoveKind::Combine)
+                .count();
+            if constraints.accepts(ratio.clone(), &initial, combines) {
+                return Ok(Self {
+                    difficulty: Difficulty::rate(stage, ratio.clone()),
+                    ratio,
+                    steps,
+                });
             }
         }
+        Err(ConstraintError::GaveUp {
+            attempts: constraints.max_attempts,
+        })
+    }
+}
+
+/// Returns the random number generator used for seeded generation.
+///
+/// [`ChaCha8Rng`] yields the same stream on every platform, including wasm,
+/// so a seed always reproduces the same stage.
+#[inline]
+pub fn seeded_rng(seed: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
+
+/// Returns a new seed to be passed to [`generate_stage_from_seed`].
+#[inline]
+pub fn random_seed() -> u64 {
+    rand::thread_rng().gen()
+}
+
+/// Generates both the ratios and the answer of a stage from `seed`.
+///
+/// Fails only if the calculator leaves the drawn integers without an answer; see [`AnswerInfo::generate_on_stage`].
+pub fn generate_stage_from_seed<const STAGE_SIZE: usize, Calc: Calculator + Default + Clone + 'static>(
+    seed: u64
+) -> Result<(RatioArray<STAGE_SIZE>, AnswerInfo), ConstraintError> {
+    let mut rng = seeded_rng(seed);
+    let ratios = generate_random_ratios_with_rng::<STAGE_SIZE>(&mut rng);
+    let answer = AnswerInfo::generate_with_rng::<STAGE_SIZE, Calc>(ratios, &mut rng)?;
+    Ok((ratios, answer))
+}
+
+/// The error returned by [`generate_constrained_stage_from_seed`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum GenerateError {
+    Pool(PoolError),
+    Constraints(ConstraintError),
+}
+
+impl Display for GenerateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenerateError::Pool(e) => write!(f, "{e}"),
+            GenerateError::Constraints(e) => write!(f, "{e}"),
+        }
     }
 }
 
+impl std::error::Error for GenerateError {}
+
+/// Generates a stage from `seed` whose cells are drawn from `pool` and whose answer meets `constraints`.
+///
+/// With [`NumberPool::for_cells`] and the default constraints,
+/// this generates the same stage as [`generate_stage_from_seed`].
+pub fn generate_constrained_stage_from_seed<const STAGE_SIZE: usize, Calc: Calculator + Default + Clone + 'static, Num: PuzzleNumber>(
+    seed: u64,
+    pool: &NumberPool<Num>,
+    constraints: &GeneratorConstraints<Num>,
+) -> Result<(RatioArray<STAGE_SIZE, Num>, AnswerInfo<Num>), GenerateError> {
+    let mut rng = seeded_rng(seed);
+    let ratios = pool.draw::<STAGE_SIZE>(&mut rng).map_err(GenerateError::Pool)?;
+    let answer = AnswerInfo::generate_constrained(&Stage::new(Calc::default(), ratios.clone()), constraints, &mut rng)
+        .map_err(GenerateError::Constraints)?;
+    Ok((ratios, answer))
+}
+
 pub fn generate_random_ratios<const STAGE_SIZE: usize>() -> RatioArray<STAGE_SIZE> {
-    let mut rng = rand::thread_rng();
-    let mut nums: Vec<isize> = (1..=10).collect();
-    nums.shuffle(&mut rng);
-    let mut ratios = [Ratio::from(0); STAGE_SIZE];
-    ratios.iter_mut().for_each(|r| {
-        *r = Ratio::from(nums.pop().unwrap());
-    });
-    ratios
+    generate_random_ratios_with_rng(&mut rand::thread_rng())
+}
+
+#[inline]
+pub fn generate_random_ratios_from_seed<const STAGE_SIZE: usize>(seed: u64) -> RatioArray<STAGE_SIZE> {
+    generate_random_ratios_with_rng(&mut seeded_rng(seed))
 }
 
-fn try_generate<const STAGE_SIZE: usize, Calc: Calculator + 'static>(stage: &mut Stage<STAGE_SIZE, Calc>, rng: &mut ThreadRng) -> Option<AnswerInfo> {
+/// Draws distinct integers from [`NumberPool::for_cells`], so any number of cells can be filled.
+pub fn generate_random_ratios_with_rng<const STAGE_SIZE: usize>(rng: &mut impl Rng) -> RatioArray<STAGE_SIZE> {
+    NumberPool::for_cells(STAGE_SIZE)
+        .draw(rng)
+        .expect("the pool has a value for every cell")
+}
+
+fn try_generate<const STAGE_SIZE: usize, Calc: Calculator + 'static, Num: PuzzleNumber>(stage: &mut Stage<STAGE_SIZE, Calc, Num>, rng: &mut impl Rng) -> Option<(Num, Steps)> {
     let mut steps = Steps::default();
     loop {
-        let mut indices = stage.movable_indices();
-        indices.shuffle(rng);
-
-        let cell_no = indices.pop()?;
-        let mut dirs = stage.movable_dirs(cell_no);
-        dirs.shuffle(rng);
-        let dir = dirs.pop()?;
+        let (cell_no, dir) = stage
+            .legal_moves()
+            .choose(rng)
+            .copied()?;
+        stage.move_cell(cell_no, dir).ok()?;
         steps.push(cell_no, dir);
-        stage.move_cell(cell_no, dir);
 
         if let Some(r) = stage.last_ratio() {
             // for (i, step) in steps.iter().enumerate() {
             //     println!("[{i}] no: {} dir: {:?}\n", step.0, step.1);
             // }
-            return Some(AnswerInfo {
-                ratio: r,
-                steps,
-            });
+            return Some((r, steps));
         }
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroIsize;
+
+    use crate::answer::{AnswerInfo, generate_constrained_stage_from_seed, generate_random_ratios_from_seed, generate_stage_from_seed, seeded_rng};
+    use crate::answer::constraints::{ConstraintError, GeneratorConstraints};
+    use crate::answer::pool::NumberPool;
+    use crate::answer::GenerateError;
+    use crate::answer::steps::Steps;
+    use crate::calculator::small_size::SmallSizeCalculator;
+    use crate::difficulty::{Difficulty, DifficultyBand};
+    use crate::move_dir::MoveDir;
+    use crate::operation::Operation;
+    use crate::ratio::Ratio;
+    use crate::rule_set::RuleSet;
+    use crate::stage::Stage;
+
+    #[test]
+    fn report_difficulty() {
+        let ratios = [1, 2, 3, 4].map(Ratio::from);
+        let answer = AnswerInfo::generate::<4, SmallSizeCalculator>(ratios).unwrap();
+        let stage = Stage::<4, SmallSizeCalculator>::from(ratios);
+        assert!(answer.difficulty.is_some());
+        assert_eq!(answer.difficulty, Difficulty::rate(&stage, answer.ratio));
+    }
+
+    #[test]
+    fn generate_in_band() {
+        let ratios = [1, 2, 3, 4].map(Ratio::from);
+        let answer = AnswerInfo::generate_in_band::<4, SmallSizeCalculator>(ratios, DifficultyBand::Hard).unwrap();
+        assert_eq!(answer.difficulty.unwrap().band(), DifficultyBand::Hard);
+    }
+
+    #[test]
+    fn same_seed_same_stage() {
+        let (ratios1, answer1) = generate_stage_from_seed::<4, SmallSizeCalculator>(7).unwrap();
+        let (ratios2, answer2) = generate_stage_from_seed::<4, SmallSizeCalculator>(7).unwrap();
+        assert_eq!(ratios1, ratios2);
+        assert_eq!(answer1.ratio, answer2.ratio);
+        assert_eq!(answer1.steps, answer2.steps);
+    }
+
+    #[test]
+    fn same_seed_same_answer() {
+        let ratios = generate_random_ratios_from_seed::<4>(3);
+        assert_eq!(ratios, generate_random_ratios_from_seed::<4>(3));
+        let answer1 = AnswerInfo::generate_from_seed::<4, SmallSizeCalculator>(ratios, 3).unwrap();
+        let answer2 = AnswerInfo::generate_from_seed::<4, SmallSizeCalculator>(ratios, 3).unwrap();
+        assert_eq!(answer1.ratio, answer2.ratio);
+        assert_eq!(answer1.steps, answer2.steps);
+    }
+
+    #[test]
+    fn never_overflow() {
+        let ratios = [isize::MAX, isize::MAX - 1, 2, 3].map(Ratio::from);
+        for seed in 0..16 {
+            let answer = AnswerInfo::generate_from_seed::<4, SmallSizeCalculator>(ratios, seed).unwrap();
+            let stage = Stage::<4, SmallSizeCalculator>::from(ratios);
+            assert_eq!(answer.steps.verify(&stage, answer.ratio), Ok(answer.ratio));
+        }
+    }
+
+    #[test]
+    fn generate_with_rules() {
+        let rules = RuleSet::new(Operation::Concat);
+        let stage = Stage::with_rules(SmallSizeCalculator, rules, [1, 2, 3, 4].map(Ratio::from));
+        let answer = AnswerInfo::generate_on_stage(&stage, &mut seeded_rng(1)).unwrap();
+        assert_eq!(answer.steps.verify(&stage, answer.ratio), Ok(answer.ratio));
+        let mut stage = stage.clone();
+        for (cell_no, dir) in &answer.steps {
+            let op = stage.operation(*cell_no, *dir);
+            assert!(op == Some(Operation::Concat) || op == Some(Operation::Swap));
+            stage.move_cell(*cell_no, *dir).unwrap();
+        }
+    }
+
+    #[test]
+    fn constrained() {
+        let constraints = GeneratorConstraints {
+            integer_goal: true,
+            non_negative_goal: true,
+            new_goal: true,
+            max_goal: Some(Ratio::from(30)),
+            ..Default::default()
+        };
+        for seed in 0..16 {
+            let (ratios, answer) = generate_constrained_stage_from_seed::<4, SmallSizeCalculator, _>(seed, &NumberPool::default(), &constraints).unwrap();
+            assert!(constraints.accepts_goal(answer.ratio, &ratios.map(Some)));
+            let stage = Stage::<4, SmallSizeCalculator>::from(ratios);
+            assert_eq!(answer.steps.verify(&stage, answer.ratio), Ok(answer.ratio));
+        }
+    }
+
+    #[test]
+    fn unconstrained_is_unchanged() {
+        for seed in [3, 42] {
+            assert_eq!(
+                generate_constrained_stage_from_seed::<4, SmallSizeCalculator, _>(seed, &NumberPool::default(), &GeneratorConstraints::default()),
+                generate_stage_from_seed::<4, SmallSizeCalculator>(seed).map_err(GenerateError::Constraints)
+            );
+        }
+    }
+
+    #[test]
+    fn give_up() {
+        let constraints = GeneratorConstraints {
+            min_goal: Some(Ratio::from(1_000_000)),
+            max_attempts: 8,
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_constrained_stage_from_seed::<4, SmallSizeCalculator, _>(1, &NumberPool::default(), &constraints),
+            Err(GenerateError::Constraints(ConstraintError::GaveUp { attempts: 8 }))
+        );
+        let constraints = GeneratorConstraints {
+            min_combines: 4,
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_constrained_stage_from_seed::<4, SmallSizeCalculator, _>(1, &NumberPool::default(), &constraints),
+            Err(GenerateError::Constraints(ConstraintError::Contradictory))
+        );
+    }
+
+    #[test]
+    fn fractions_only() {
+        let pool = NumberPool::fractions(0, 3, 4).unwrap();
+        let (ratios, answer) = generate_constrained_stage_from_seed::<4, SmallSizeCalculator, _>(5, &pool, &GeneratorConstraints::default()).unwrap();
+        assert!(ratios.iter().all(|r| r.to_integer().is_none()));
+        let stage = Stage::<4, SmallSizeCalculator>::from(ratios);
+        assert_eq!(answer.steps.verify(&stage, answer.ratio), Ok(answer.ratio));
+    }
+
+    /// The seeded stream must never change, otherwise shared seeds would point to other stages.
+    #[test]
+    fn seed_regression() {
+        let (ratios, answer) = generate_stage_from_seed::<4, SmallSizeCalculator>(42).unwrap();
+        assert_eq!(ratios, [1, 3, 9, 4].map(Ratio::from));
+        assert_eq!(answer.ratio, Ratio::new(4, NonZeroIsize::new(5).unwrap()));
+        assert_eq!(answer.steps, Steps::from_iter([
+            (2, MoveDir::Up),
+            (0, MoveDir::Right),
+            (0, MoveDir::Right),
+            (2, MoveDir::LeftUp),
+            (0, MoveDir::RightUp),
+            (1, MoveDir::RightDown),
+        ]));
+    }
+}
@@ -0,0 +1,170 @@
+//! Constraints on the goal and the answer chosen by the generator.
+
+use std::fmt::{Display, Formatter};
+
+use crate::number::PuzzleNumber;
+use crate::ratio::Ratio;
+
+/// The number of random walks tried by default before giving up on the constraints.
+pub const DEFAULT_ATTEMPTS: usize = 256;
+
+/// What the generator may accept as the goal and its answer.
+///
+/// The default accepts anything, like the unconstrained generator.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeneratorConstraints<Num = Ratio> {
+    /// Rejects fractional goals.
+    pub integer_goal: bool,
+    /// The smallest goal allowed, inclusive.
+    pub min_goal: Option<Num>,
+    /// The largest goal allowed, inclusive.
+    pub max_goal: Option<Num>,
+    /// Rejects negative goals.
+    pub non_negative_goal: bool,
+    /// Rejects goals equal to one of the cells the answer starts from.
+    pub new_goal: bool,
+    /// The smallest number of combines in the answer.
+    ///
+    /// Reaching a single cell from `n` cells always takes `n - 1` combines,
+    /// so this only rules out stages that start with empty cells.
+    pub min_combines: usize,
+    /// The number of random walks tried before giving up.
+    pub max_attempts: usize,
+}
+
+impl<Num> Default for GeneratorConstraints<Num> {
+    fn default() -> Self {
+        Self {
+            integer_goal: false,
+            min_goal: None,
+            max_goal: None,
+            non_negative_goal: false,
+            new_goal: false,
+            min_combines: 0,
+            max_attempts: DEFAULT_ATTEMPTS,
+        }
+    }
+}
+
+impl<Num: PuzzleNumber> GeneratorConstraints<Num> {
+    /// Fails with [`ConstraintError::Contradictory`] if no answer from `cells` non-empty cells can meet the constraints.
+    pub fn check(&self, cells: usize) -> Result<(), ConstraintError> {
+        let empty_range = self.min_goal
+            .as_ref()
+            .zip(self.max_goal.as_ref())
+            .is_some_and(|(min, max)| max < min);
+        let negative_range = self.non_negative_goal && self.max_goal.as_ref().is_some_and(Num::is_negative);
+        if empty_range || negative_range || cells.saturating_sub(1) < self.min_combines {
+            Err(ConstraintError::Contradictory)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns whether `goal` is acceptable for an answer that starts from `initial`.
+    pub fn accepts_goal(&self, goal: Num, initial: &[Option<Num>]) -> bool {
+        (!self.integer_goal || goal.to_integer().is_some())
+            && self.min_goal.as_ref().is_none_or(|min| *min <= goal)
+            && self.max_goal.as_ref().is_none_or(|max| goal <= *max)
+            && (!self.non_negative_goal || !goal.is_negative())
+            && (!self.new_goal || initial.iter().flatten().all(|cell| *cell != goal))
+    }
+
+    /// Returns whether an answer that reaches `goal` from `initial` with `combines` combines is acceptable.
+    #[inline]
+    pub fn accepts(&self, goal: Num, initial: &[Option<Num>], combines: usize) -> bool {
+        self.min_combines <= combines && self.accepts_goal(goal, initial)
+    }
+}
+
+/// The error returned when the generator can't meet its [`GeneratorConstraints`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ConstraintError {
+    /// The constraints contradict each other or the stage, so nothing was tried.
+    Contradictory,
+    /// None of the `attempts` random walks met the constraints.
+    GaveUp {
+        attempts: usize,
+    },
+}
+
+impl Display for ConstraintError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConstraintError::Contradictory => f.write_str("the goal constraints can't be met by any answer"),
+            ConstraintError::GaveUp { attempts } => write!(f, "no answer met the goal constraints within {attempts} attempts"),
+        }
+    }
+}
+
+impl std::error::Error for ConstraintError {}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroIsize;
+
+    use crate::answer::constraints::{ConstraintError, GeneratorConstraints};
+    use crate::ratio::Ratio;
+
+    #[test]
+    fn accepts_goal() {
+        let initial = [1, 2, 3, 4].map(|n| Some(Ratio::from(n)));
+        let half = Ratio::new(1, NonZeroIsize::new(2).unwrap());
+        assert!(GeneratorConstraints::default().accepts_goal(half, &initial));
+
+        let constraints = GeneratorConstraints {
+            integer_goal: true,
+            ..Default::default()
+        };
+        assert!(!constraints.accepts_goal(half, &initial));
+        assert!(constraints.accepts_goal(Ratio::from(-3), &initial));
+
+        let constraints = GeneratorConstraints {
+            non_negative_goal: true,
+            new_goal: true,
+            ..Default::default()
+        };
+        assert!(!constraints.accepts_goal(Ratio::from(-3), &initial));
+        assert!(!constraints.accepts_goal(Ratio::from(4), &initial));
+        assert!(!constraints.accepts_goal(Ratio::new(1, NonZeroIsize::new(-2).unwrap()), &initial));
+        assert!(constraints.accepts_goal(Ratio::from(0), &initial));
+        assert!(constraints.accepts_goal(Ratio::from(4), &initial[..3]));
+    }
+
+    #[test]
+    fn goal_range() {
+        let constraints = GeneratorConstraints {
+            min_goal: Some(Ratio::from(-2)),
+            max_goal: Some(Ratio::from(10)),
+            ..Default::default()
+        };
+        assert!(constraints.accepts_goal(Ratio::from(-2), &[]));
+        assert!(constraints.accepts_goal(Ratio::from(10), &[]));
+        assert!(!constraints.accepts_goal(Ratio::from(11), &[]));
+        assert!(!constraints.accepts_goal(Ratio::new(5, NonZeroIsize::new(-2).unwrap()), &[]));
+    }
+
+    #[test]
+    fn contradictory() {
+        assert_eq!(GeneratorConstraints::<Ratio>::default().check(4), Ok(()));
+        let constraints = GeneratorConstraints {
+            min_goal: Some(Ratio::from(5)),
+            max_goal: Some(Ratio::from(1)),
+            ..Default::default()
+        };
+        assert_eq!(constraints.check(4), Err(ConstraintError::Contradictory));
+        let constraints = GeneratorConstraints {
+            max_goal: Some(Ratio::from(-1)),
+            non_negative_goal: true,
+            ..Default::default()
+        };
+        assert_eq!(constraints.check(4), Err(ConstraintError::Contradictory));
+        let constraints = GeneratorConstraints::<Ratio> {
+            min_combines: 3,
+            ..Default::default()
+        };
+        assert_eq!(constraints.check(4), Ok(()));
+        assert_eq!(constraints.check(3), Err(ConstraintError::Contradictory));
+    }
+}
@@ -0,0 +1,373 @@
+//! The values the cells of a generated stage start with.
+
+use std::fmt::{Display, Formatter};
+use std::num::NonZeroIsize;
+
+use rand::prelude::{IndexedRandom, SliceRandom};
+use rand::Rng;
+
+use crate::number::PuzzleNumber;
+use crate::ratio::Ratio;
+use crate::stage::RatioArray;
+
+/// The most values [`NumberPool::range`] and [`NumberPool::fractions`] may create,
+/// as every value is stored.
+pub const MAX_POOL_VALUES: usize = 10_000;
+
+/// The values the starting cells are drawn from, each with a weight.
+///
+/// A value with twice the weight of another is drawn twice as often.
+/// Without duplicates, a drawn value is taken out of the pool until the stage is filled.
+///
+/// Deserializing merges repeated values and drops values of zero weight, like [`NumberPool::weighted`],
+/// and rejects a pool left empty.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(
+    try_from = "RawNumberPool<Num>",
+    bound(deserialize = "Num: PuzzleNumber + serde::Deserialize<'de>"),
+))]
+pub struct NumberPool<Num = Ratio> {
+    /// Distinct values with positive weights, in the order they were added.
+    values: Vec<(Num, u32)>,
+    /// Whether several cells may start with the same value.
+    pub duplicates: bool,
+}
+
+impl Default for NumberPool {
+    /// Distinct integers from 1 to 10, which is what stages have always started with.
+    #[inline]
+    fn default() -> Self {
+        Self::range(1, 10).expect("ten values are within the limit")
+    }
+}
+
+impl NumberPool {
+    /// Returns the pool used by default for a board of `cells` cells:
+    /// distinct integers from 1 to 10, or to `cells` if there are more cells than that.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `cells` is more than [`MAX_POOL_VALUES`].
+    pub fn for_cells(cells: usize) -> Self {
+        Self::range(1, isize::try_from(cells).unwrap_or(isize::MAX).max(10))
+            .expect("a board has fewer cells than the pool limit")
+    }
+
+    /// Returns the integers from `min` to `max` inclusive, all of the same weight.
+    ///
+    /// Fails with [`PoolError::TooManyValues`] if there are more than [`MAX_POOL_VALUES`] of them.
+    pub fn range(min: isize, max: isize) -> Result<Self, PoolError> {
+        check_span(min, max, 0)?;
+        Ok(Self::distinct((min..=max).map(Ratio::from)))
+    }
+
+    /// Returns the values from `min` to `max` inclusive that are fractions with a denominator up to `max_denom`,
+    /// all of the same weight. Integers are left out.
+    ///
+    /// Fails with [`PoolError::TooManyValues`] if more than [`MAX_POOL_VALUES`] numerators would be tried
+    /// over all the denominators, even if most of them aren't kept.
+    pub fn fractions(min: isize, max: isize, max_denom: isize) -> Result<Self, PoolError> {
+        if max < min {
+            return Ok(Self::distinct([]));
+        }
+        let too_many = PoolError::TooManyValues {
+            limit: MAX_POOL_VALUES,
+        };
+        let mut values = Vec::new();
+        let mut tried = 0;
+        for denom in 2..=max_denom {
+            let lo = min.checked_mul(denom).ok_or(too_many)?;
+            let hi = max.checked_mul(denom).ok_or(too_many)?;
+            check_span(lo, hi, tried)?;
+            tried += hi.abs_diff(lo) + 1;
+            let denom = NonZeroIsize::new(denom).expect("denominators start from 2");
+            values.extend((lo..=hi)
+                .map(|numer| Ratio::new(numer, denom))
+                .filter(|ratio| ratio.denom == denom.get()));
+        }
+        values.sort();
+        Ok(Self::distinct(values))
+    }
+}
+
+impl<Num: PuzzleNumber> NumberPool<Num> {
+    /// Returns a pool of `entries`; the weights of a value given more than once are added up,
+    /// and values of zero weight are left out.
+    pub fn weighted(entries: impl IntoIterator<Item = (Num, u32)>) -> Self {
+        let mut pool = Self {
+            values: Vec::new(),
+            duplicates: false,
+        };
+        for (value, weight) in entries {
+            pool.add(value, weight);
+        }
+        pool
+    }
+
+    /// Collects `values` known to be distinct with weight 1 each, without looking for repeats.
+    fn distinct(values: impl IntoIterator<Item = Num>) -> Self {
+        Self {
+            values: values.into_iter().map(|value| (value, 1)).collect(),
+            duplicates: false,
+        }
+    }
+
+    /// Adds the values of `other` to this pool, adding up their weights.
+    pub fn chain(mut self, other: Self) -> Self {
+        for (value, weight) in other.values {
+            self.add(value, weight);
+        }
+        self
+    }
+
+    #[inline]
+    pub fn with_duplicates(mut self, duplicates: bool) -> Self {
+        self.duplicates = duplicates;
+        self
+    }
+
+    /// Returns the distinct values and their weights.
+    #[inline]
+    pub fn values(&self) -> &[(Num, u32)] {
+        &self.values
+    }
+
+    /// Fails if this pool can't fill a stage of `cells` cells.
+    pub fn check(&self, cells: usize) -> Result<(), PoolError> {
+        if self.values.is_empty() {
+            Err(PoolError::Empty)
+        } else if !self.duplicates && self.values.len() < cells {
+            Err(PoolError::TooFewValues {
+                values: self.values.len(),
+                cells,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Draws the starting values of a stage.
+    pub fn draw<const STAGE_SIZE: usize>(&self, rng: &mut impl Rng) -> Result<RatioArray<STAGE_SIZE, Num>, PoolError> {
+        self.check(STAGE_SIZE)?;
+        let mut ratios = Vec::with_capacity(STAGE_SIZE);
+        let uniform = self.values.windows(2).all(|w| w[0].1 == w[1].1);
+        if uniform && self.duplicates {
+            for _ in 0..STAGE_SIZE {
+                ratios.push(self.values.choose(rng).expect("the pool isn't empty").0.clone());
+            }
+        } else if uniform {
+            // Kept as it has always been done, so that seeds keep reproducing the same stages.
+            let mut values = self.values.iter().map(|(value, _)| value.clone()).collect::<Vec<_>>();
+            values.shuffle(rng);
+            for _ in 0..STAGE_SIZE {
+                ratios.push(values.pop().expect("checked to be enough"));
+            }
+        } else {
+            let mut values = self.values.clone();
+            for _ in 0..STAGE_SIZE {
+                let i = choose_weighted(&values, rng)?;
+                ratios.push(values[i].0.clone());
+                if !self.duplicates {
+                    values.remove(i);
+                }
+            }
+        }
+        Ok(ratios.try_into().unwrap_or_else(|_| unreachable!("a value is drawn for every cell")))
+    }
+
+    fn add(&mut self, value: Num, weight: u32) {
+        if weight == 0 {
+            return;
+        }
+        match self.values.iter_mut().find(|(v, _)| *v == value) {
+            Some((_, w)) => *w = w.saturating_add(weight),
+            None => self.values.push((value, weight)),
+        }
+    }
+}
+
+impl<Num: PuzzleNumber> FromIterator<Num> for NumberPool<Num> {
+    /// Collects values of weight 1 each.
+    fn from_iter<T: IntoIterator<Item = Num>>(iter: T) -> Self {
+        Self::weighted(iter.into_iter().map(|value| (value, 1)))
+    }
+}
+
+/// The form a [`NumberPool`] is deserialized from before it is checked.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawNumberPool<Num> {
+    values: Vec<(Num, u32)>,
+    duplicates: bool,
+}
+
+#[cfg(feature = "serde")]
+impl<Num: PuzzleNumber> TryFrom<RawNumberPool<Num>> for NumberPool<Num> {
+    type Error = PoolError;
+
+    fn try_from(raw: RawNumberPool<Num>) -> Result<Self, Self::Error> {
+        let pool = Self::weighted(raw.values).with_duplicates(raw.duplicates);
+        if pool.values.is_empty() {
+            Err(PoolError::Empty)
+        } else {
+            Ok(pool)
+        }
+    }
+}
+
+/// Fails if trying the values of `min..=max` after `tried` others goes over [`MAX_POOL_VALUES`].
+fn check_span(min: isize, max: isize, tried: usize) -> Result<(), PoolError> {
+    let span = (max as i128 - min as i128 + 1).max(0);
+    let values = span.saturating_add(tried as i128);
+    if values > MAX_POOL_VALUES as i128 {
+        Err(PoolError::TooManyValues {
+            limit: MAX_POOL_VALUES,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns the index of a value picked with a probability proportional to its weight.
+///
+/// Fails with [`PoolError::Empty`] if no value has a positive weight.
+fn choose_weighted<Num>(values: &[(Num, u32)], rng: &mut impl Rng) -> Result<usize, PoolError> {
+    let total = values.iter().map(|(_, weight)| u64::from(*weight)).sum::<u64>();
+    if total == 0 {
+        return Err(PoolError::Empty);
+    }
+    let mut pick = rng.gen_range(0..total);
+    for (i, (_, weight)) in values.iter().enumerate() {
+        match pick.checked_sub(u64::from(*weight)) {
+            Some(rest) => pick = rest,
+            None => return Ok(i),
+        }
+    }
+    unreachable!("the pick is below the total weight")
+}
+
+/// The error returned when a [`NumberPool`] can't fill a stage.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum PoolError {
+    /// The pool has no value of positive weight.
+    Empty,
+    /// Duplicates aren't allowed, and there are fewer distinct values than cells.
+    TooFewValues {
+        values: usize,
+        cells: usize,
+    },
+    /// The range asked for has more than `limit` values.
+    TooManyValues {
+        limit: usize,
+    },
+}
+
+impl Display for PoolError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PoolError::Empty => f.write_str("the number pool is empty"),
+            PoolError::TooFewValues { values, cells } => {
+                write!(f, "the number pool has {values} distinct values for {cells} cells; allow duplicates or widen it")
+            }
+            PoolError::TooManyValues { limit } => write!(f, "the number pool would have more than {limit} values; narrow it"),
+        }
+    }
+}
+
+impl std::error::Error for PoolError {}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroIsize;
+
+    use crate::answer::pool::{choose_weighted, MAX_POOL_VALUES, NumberPool, PoolError};
+    use crate::answer::seeded_rng;
+    use crate::ratio::Ratio;
+
+    #[test]
+    fn distinct_values() {
+        let mut rng = seeded_rng(1);
+        for _ in 0..32 {
+            let ratios = NumberPool::range(-2, 2).unwrap().draw::<5>(&mut rng).unwrap();
+            let mut values = ratios.map(|r| r.numer);
+            values.sort();
+            assert_eq!(values, [-2, -1, 0, 1, 2]);
+        }
+        assert_eq!(NumberPool::range(-2, 2).unwrap().draw::<6>(&mut rng), Err(PoolError::TooFewValues {
+            values: 5,
+            cells: 6,
+        }));
+        assert_eq!(NumberPool::range(1, 0).unwrap().draw::<1>(&mut rng), Err(PoolError::Empty));
+    }
+
+    #[test]
+    fn duplicates() {
+        let pool = NumberPool::range(3, 4).unwrap().with_duplicates(true);
+        let ratios = pool.draw::<16>(&mut seeded_rng(1)).unwrap();
+        assert!(ratios.iter().all(|r| *r == Ratio::from(3) || *r == Ratio::from(4)));
+    }
+
+    #[test]
+    fn large_boards() {
+        let ratios = NumberPool::for_cells(12).draw::<12>(&mut seeded_rng(1)).unwrap();
+        let mut values = ratios.map(|r| r.numer);
+        values.sort();
+        assert_eq!(values, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+    }
+
+    #[test]
+    fn fractions() {
+        let pool = NumberPool::fractions(0, 1, 3).unwrap();
+        let half = Ratio::new(1, NonZeroIsize::new(2).unwrap());
+        let third = Ratio::new(1, NonZeroIsize::new(3).unwrap());
+        let two_thirds = Ratio::new(2, NonZeroIsize::new(3).unwrap());
+        assert_eq!(pool.values(), [(third, 1), (half, 1), (two_thirds, 1)]);
+    }
+
+    #[test]
+    fn weighted() {
+        let pool = NumberPool::weighted([(Ratio::from(1), 0), (Ratio::from(2), 3), (Ratio::from(5), 1), (Ratio::from(2), 1)]);
+        assert_eq!(pool.values(), [(Ratio::from(2), 4), (Ratio::from(5), 1)]);
+        let pool = pool.with_duplicates(true);
+        let mut rng = seeded_rng(1);
+        let twos = (0..100)
+            .flat_map(|_| pool.draw::<10>(&mut rng).unwrap())
+            .filter(|r| *r == Ratio::from(2))
+            .count();
+        // 800 are expected.
+        assert!((700..900).contains(&twos), "{twos}");
+    }
+
+    #[test]
+    fn too_many_values() {
+        let limit = MAX_POOL_VALUES as isize;
+        assert!(NumberPool::range(1, limit).is_ok());
+        assert_eq!(NumberPool::range(0, limit), Err(PoolError::TooManyValues { limit: MAX_POOL_VALUES }));
+        assert_eq!(NumberPool::range(isize::MIN, isize::MAX), Err(PoolError::TooManyValues { limit: MAX_POOL_VALUES }));
+        assert_eq!(NumberPool::fractions(0, limit / 4, 4), Err(PoolError::TooManyValues { limit: MAX_POOL_VALUES }));
+        // Hardly any numerator is kept, but every one of them is tried.
+        assert_eq!(NumberPool::fractions(0, 0, 100_000_000), Err(PoolError::TooManyValues { limit: MAX_POOL_VALUES }));
+        assert_eq!(NumberPool::fractions(1, 1, isize::MAX), Err(PoolError::TooManyValues { limit: MAX_POOL_VALUES }));
+        assert_eq!(NumberPool::fractions(isize::MAX, isize::MAX, 2), Err(PoolError::TooManyValues { limit: MAX_POOL_VALUES }));
+        assert!(NumberPool::fractions(1, 0, isize::MAX).unwrap().values().is_empty());
+    }
+
+    #[test]
+    fn zero_total_weight() {
+        let values = [(Ratio::from(1), 0), (Ratio::from(2), 0)];
+        assert_eq!(choose_weighted(&values, &mut seeded_rng(1)), Err(PoolError::Empty));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_validates() {
+        let pool = serde_json::from_str::<NumberPool>(r#"{"values":[[{"numer":2,"denom":1},1],[{"numer":2,"denom":1},2],[{"numer":3,"denom":1},0]],"duplicates":false}"#).unwrap();
+        assert_eq!(pool.values(), [(Ratio::from(2), 3)]);
+        assert!(serde_json::from_str::<NumberPool>(r#"{"values":[[{"numer":2,"denom":1},0]],"duplicates":true}"#).is_err());
+        assert!(serde_json::from_str::<NumberPool>(r#"{"values":[],"duplicates":true}"#).is_err());
+
+        let pool = NumberPool::range(1, 4).unwrap().with_duplicates(true);
+        assert_eq!(serde_json::from_str::<NumberPool>(&serde_json::to_string(&pool).unwrap()).unwrap(), pool);
+    }
+}
@@ -1,22 +1,241 @@
-use std::collections::VecDeque;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::Index;
+use std::slice::SliceIndex;
+
+use crate::calculator::Calculator;
 use crate::move_dir::MoveDir;
+use crate::number::PuzzleNumber;
+use crate::operation::Operation;
+use crate::ratio::Ratio;
+use crate::stage::outcome::MoveError;
+use crate::stage::Stage;
 
-#[derive(Debug, Clone, Eq, PartialEq, Default)]
-pub struct Steps(VecDeque<(usize, MoveDir)>);
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Steps(Vec<(usize, MoveDir)>);
 
 impl Steps{
     #[inline]
     pub fn push(&mut self, cell_no: usize, dir: MoveDir){
-        self.0.push_back((cell_no, dir));
+        self.0.push((cell_no, dir));
     }
 
-    #[inline]
     pub fn pop_front(&mut self) -> Option<(usize, MoveDir)>{
-        self.0.pop_front()
+        (!self.0.is_empty()).then(|| self.0.remove(0))
     }
-    
+
     #[inline]
     pub fn is_empty(&self) -> bool{
         self.0.is_empty()
     }
-}
\ No newline at end of file
+
+    #[inline]
+    pub fn len(&self) -> usize{
+        self.0.len()
+    }
+
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&(usize, MoveDir)> {
+        self.0.get(index)
+    }
+
+    #[inline]
+    pub fn iter(&self) -> std::slice::Iter<'_, (usize, MoveDir)> {
+        self.0.iter()
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[(usize, MoveDir)] {
+        &self.0
+    }
+
+    /// Plays the steps on a copy of `stage` and returns the value left at the end.
+    ///
+    /// Fails at the first step that can't be played,
+    /// or if the steps don't end with only `goal` left.
+    pub fn verify<const STAGE_SIZE: usize, Calc, Num>(
+        &self,
+        stage: &Stage<STAGE_SIZE, Calc, Num>,
+        goal: Num,
+    ) -> Result<Num, VerifyError<Num>>
+        where Calc: Calculator + Clone + 'static,
+              Num: PuzzleNumber
+    {
+        let mut stage = stage.clone();
+        for (index, (cell_no, dir)) in self.iter().enumerate() {
+            stage
+                .move_cell(*cell_no, *dir)
+                .map_err(|error| VerifyError::IllegalStep { index, error })?;
+        }
+        match stage.last_ratio() {
+            Some(ratio) if ratio == goal => Ok(ratio),
+            Some(ratio) => Err(VerifyError::WrongValue(ratio)),
+            None => Err(VerifyError::Unfinished),
+        }
+    }
+
+    /// Returns an object that displays each step played on `stage` as an expression, such as `2 LU: 1 + 3 = 4`.
+    #[inline]
+    pub fn display<'a, const STAGE_SIZE: usize, Calc, Num>(&'a self, stage: &'a Stage<STAGE_SIZE, Calc, Num>) -> StepsDisplay<'a, STAGE_SIZE, Calc, Num>
+        where Calc: Calculator,
+              Num: PuzzleNumber
+    {
+        StepsDisplay {
+            steps: self,
+            stage,
+        }
+    }
+}
+
+impl FromIterator<(usize, MoveDir)> for Steps {
+    fn from_iter<T: IntoIterator<Item=(usize, MoveDir)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for Steps {
+    type Item = (usize, MoveDir);
+    type IntoIter = std::vec::IntoIter<(usize, MoveDir)>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Steps {
+    type Item = &'a (usize, MoveDir);
+    type IntoIter = std::slice::Iter<'a, (usize, MoveDir)>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<I: SliceIndex<[(usize, MoveDir)]>> Index<I> for Steps {
+    type Output = I::Output;
+
+    #[inline]
+    fn index(&self, index: I) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+/// The reason [`Steps::verify`] failed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum VerifyError<Num = Ratio> {
+    /// The step at `index` can't be played.
+    IllegalStep {
+        index: usize,
+        error: MoveError,
+    },
+    /// All the steps were played, but more than one cell is left.
+    Unfinished,
+    /// All the steps were played, but the value left isn't the goal.
+    WrongValue(Num),
+}
+
+impl<Num: Display> Display for VerifyError<Num> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VerifyError::IllegalStep { index, error } => write!(f, "step {} can't be played: {error}", index + 1),
+            VerifyError::Unfinished => f.write_str("the steps leave more than one cell"),
+            VerifyError::WrongValue(ratio) => write!(f, "the steps reach {ratio}, which isn't the goal"),
+        }
+    }
+}
+
+impl<Num: Debug + Display> std::error::Error for VerifyError<Num> {}
+
+/// Displays [`Steps`] played on a stage; see [`Steps::display`].
+///
+/// Each step is written on its own line. A step that can't be played is written with the reason,
+/// and the steps after it are left out.
+pub struct StepsDisplay<'a, const STAGE_SIZE: usize, Calc: Calculator, Num: PuzzleNumber = Ratio> {
+    steps: &'a Steps,
+    stage: &'a Stage<STAGE_SIZE, Calc, Num>,
+}
+
+impl<const STAGE_SIZE: usize, Calc, Num> Display for StepsDisplay<'_, STAGE_SIZE, Calc, Num>
+    where Calc: Calculator + Clone + 'static,
+          Num: PuzzleNumber
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut stage = self.stage.clone();
+        for (i, (cell_no, dir)) in self.steps.iter().enumerate() {
+            if 0 < i {
+                writeln!(f)?;
+            }
+            write!(f, "{cell_no} {}: ", dir.abbreviation())?;
+            if let Err(e) = stage.move_cell(*cell_no, *dir) {
+                return write!(f, "{e}");
+            }
+            let record = stage.history().last().expect("a move has just been played");
+            let md = record.dist_ratio.as_ref().map(|r| r.to_string()).unwrap_or_else(|| String::from("_"));
+            let ms = &record.src_ratio;
+            match (record.operation, &record.result) {
+                (Operation::Max | Operation::Min, Some(result)) => write!(f, "{}({md}, {ms}) = {result}", record.operation.symbol())?,
+                (operation, Some(result)) => write!(f, "{md} {} {ms} = {result}", operation.symbol())?,
+                (_, None) => write!(f, "{ms} {} {md}", Operation::Swap.symbol())?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answer::steps::{Steps, VerifyError};
+    use crate::calculator::small_size::SmallSizeCalculator;
+    use crate::move_dir::MoveDir;
+    use crate::ratio::Ratio;
+    use crate::stage::outcome::MoveError;
+    use crate::stage::Stage;
+
+    #[test]
+    fn verify() {
+        let stage = stage();
+        let steps = Steps::from_iter([(2, MoveDir::LeftUp), (1, MoveDir::LeftDown), (3, MoveDir::Left)]);
+        assert_eq!(steps.verify(&stage, Ratio::from(24)), Err(VerifyError::Unfinished));
+        let steps = Steps::from_iter([(2, MoveDir::LeftUp), (2, MoveDir::LeftUp)]);
+        assert_eq!(steps.verify(&stage, Ratio::from(24)), Err(VerifyError::IllegalStep {
+            index: 1,
+            error: MoveError::EmptySource,
+        }));
+        // (2 + 4) * (1 + 3)
+        let steps = Steps::from_iter([(2, MoveDir::LeftUp), (3, MoveDir::LeftUp), (1, MoveDir::LeftDown)]);
+        assert_eq!(steps.verify(&stage, Ratio::from(12)), Err(VerifyError::WrongValue(Ratio::from(24))));
+        assert_eq!(steps.verify(&stage, Ratio::from(24)), Ok(Ratio::from(24)));
+        // The stage itself is left untouched.
+        assert_eq!(stage.position(), 0);
+    }
+
+    #[test]
+    fn index_and_slice() {
+        let steps = Steps::from_iter([(2, MoveDir::LeftUp), (1, MoveDir::Down), (0, MoveDir::Right)]);
+        assert_eq!(steps[1], (1, MoveDir::Down));
+        assert_eq!(&steps[1..], &[(1, MoveDir::Down), (0, MoveDir::Right)]);
+        assert_eq!(steps.get(3), None);
+        assert_eq!((&steps).into_iter().count(), 3);
+        assert_eq!(steps.into_iter().next(), Some((2, MoveDir::LeftUp)));
+    }
+
+    #[test]
+    fn display() {
+        let stage = stage();
+        let steps = Steps::from_iter([(2, MoveDir::LeftUp), (1, MoveDir::Down), (0, MoveDir::RightDown), (0, MoveDir::Left)]);
+        assert_eq!(steps.display(&stage).to_string(), "\
+2 LU: 1 + 3 = 4
+1 D: 2 <> _
+0 RD: 2 / 4 = 1/2
+0 L: there is no cell to move");
+    }
+
+    fn stage() -> Stage<4, SmallSizeCalculator> {
+        Stage::from([
+            1, 2, 3, 4,
+        ])
+    }
+}
@@ -1,22 +1,63 @@
-use num::FromPrimitive;
+use crate::movable_ratio::MovableRatio;
 use crate::move_dir::MoveDir;
+use crate::number::PuzzleNumber;
+use crate::rule_set::RuleSet;
 use crate::stage::StageRatioArray;
+use crate::topology::Topology;
 
 pub mod small_size;
+pub mod grid;
+pub mod custom;
+pub mod hex;
 
+/// Decides which moves are allowed on a board.
+///
+/// The board shape comes from [`Calculator::Topology`];
+/// by default a move is allowed if the destination exists
+/// and the operation the [`RuleSet`] assigns to the direction can be performed.
 pub trait Calculator {
-    fn can_move<const STAGE_SIZE: usize>(
+    type Topology: Topology;
+
+    fn topology(&self) -> &Self::Topology;
+
+    fn can_move<const STAGE_SIZE: usize, Num: PuzzleNumber>(
         &self,
-        ratios: &StageRatioArray<STAGE_SIZE>,
+        rules: &RuleSet,
+        ratios: &StageRatioArray<STAGE_SIZE, Num>,
         src_no: usize,
         dir: MoveDir,
-    ) -> bool;
-    
-    fn dir_as_isize(dir: &MoveDir) -> isize;
-    
-    fn dist_no<const STAGE_SIZE: usize>(src_no: usize, dir: &MoveDir) -> Option<usize>{
-        let dist_no = src_no as isize + Self::dir_as_isize(dir);
-        let dist_no = usize::from_isize(dist_no)?;
-        (dist_no < STAGE_SIZE).then_some(dist_no)
+    ) -> bool {
+        self.dist_no(src_no, dir)
+            .is_some_and(|dist_no| can_operate(rules, ratios, src_no, dist_no, dir))
+    }
+
+    #[inline]
+    fn dist_no(&self, src_no: usize, dir: MoveDir) -> Option<usize> {
+        self.topology().neighbour(src_no, dir)
+    }
+}
+
+/// Checks the parts of a move from `src_no` to `dist_no` that don't depend on the board shape:
+/// the source must be an unmoved cell, and the operation must neither divide by zero nor overflow.
+pub(crate) fn can_operate<const STAGE_SIZE: usize, Num: PuzzleNumber>(
+    rules: &RuleSet,
+    ratios: &StageRatioArray<STAGE_SIZE, Num>,
+    src_no: usize,
+    dist_no: usize,
+    dir: MoveDir,
+) -> bool {
+    let Some(Some(MovableRatio {
+                      moved: _n @ false,
+                      ratio: src_ratio
+                  })) = ratios.get(src_no) else {
+        return false;
+    };
+    let Some(dist_ratio) = ratios.get(dist_no) else {
+        return false;
+    };
+    if let Some(MovableRatio { ratio: dist_ratio, .. }) = dist_ratio {
+        rules.operation(dir).apply(dist_ratio.clone(), src_ratio.clone()).is_ok()
+    } else {
+        true
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,55 @@
+use crate::calculator::Calculator;
+use crate::topology::Topology;
+
+/// The calculator for any [`Topology`], such as a [`GraphTopology`](crate::topology::graph::GraphTopology).
+///
+/// This makes it possible to play on a new board shape by only describing the shape.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct CustomCalculator<T>(pub T);
+
+impl<T: Topology> Calculator for CustomCalculator<T> {
+    type Topology = T;
+
+    #[inline]
+    fn topology(&self) -> &Self::Topology {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::calculator::custom::CustomCalculator;
+    use crate::movable_ratio::MovableRatio;
+    use crate::move_dir::MoveDir;
+    use crate::ratio::Ratio;
+    use crate::stage::Stage;
+    use crate::topology::CellPos;
+    use crate::topology::graph::GraphTopology;
+
+    #[test]
+    fn play_on_graph() {
+        // 0 - 1
+        //      \
+        //       2
+        let topology = GraphTopology::new([
+            CellPos::new(-1., 0.),
+            CellPos::new(0., 0.),
+            CellPos::new(1., -1.),
+        ])
+            .connect(0, MoveDir::Right, 1)
+            .connect(1, MoveDir::RightDown, 2);
+        let mut stage = Stage::new(CustomCalculator(topology), [1, 2, 6].map(Ratio::from));
+
+        assert_eq!(stage.legal_moves(), vec![
+            (0, MoveDir::Right),
+            (1, MoveDir::Left),
+            (1, MoveDir::RightDown),
+            (2, MoveDir::LeftUp),
+        ]);
+        stage.move_cell(1, MoveDir::RightDown).unwrap();
+        assert_eq!(stage[2], Some(MovableRatio::from(3)));
+        stage.move_cell(2, MoveDir::LeftUp).unwrap();
+        assert_eq!(stage[2], None);
+        assert_eq!(stage[1], Some(MovableRatio::new_moved(Ratio::from(3))));
+    }
+}
@@ -0,0 +1,80 @@
+use crate::calculator::Calculator;
+use crate::topology::grid::GridTopology;
+
+/// The calculator for a rectangular board of `W` columns and `H` rows.
+///
+/// The stage size must be `W * H`, e.g. `Stage<9, GridCalculator<3, 3>>`.
+/// Every cell can move in all eight directions as long as the destination is on the board.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct GridCalculator<const W: usize, const H: usize>;
+
+impl<const W: usize, const H: usize> Calculator for GridCalculator<W, H> {
+    type Topology = GridTopology<W, H>;
+
+    #[inline]
+    fn topology(&self) -> &Self::Topology {
+        &GridTopology
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answer::AnswerInfo;
+    use crate::calculator::grid::GridCalculator;
+    use crate::movable_ratio::MovableRatio;
+    use crate::move_dir::MoveDir;
+    use crate::ratio::Ratio;
+    use crate::stage::Stage;
+
+    type Grid3x3 = GridCalculator<3, 3>;
+
+    #[test]
+    fn center_moves_everywhere() {
+        let stage = stage();
+        assert_eq!(stage.movable_dirs(4), MoveDir::ALL.to_vec());
+    }
+
+    #[test]
+    fn corners() {
+        let stage = stage();
+        assert_eq!(stage.movable_dirs(0), vec![MoveDir::Right, MoveDir::Down, MoveDir::RightDown]);
+        assert_eq!(stage.movable_dirs(8), vec![MoveDir::LeftUp, MoveDir::Up, MoveDir::Left]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn wrong_stage_size() {
+        let _ = Stage::<4, Grid3x3>::from([1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn add() {
+        let mut stage = stage();
+        stage.move_cell(4, MoveDir::LeftUp).unwrap();
+        assert_eq!(stage[4], None);
+        assert_eq!(stage[0], Some(MovableRatio::from(6)));
+    }
+
+    #[test]
+    fn swap_down() {
+        let mut stage = stage();
+        stage.move_cell(4, MoveDir::Down).unwrap();
+        assert_eq!(stage[4], Some(MovableRatio::from(8)));
+        assert_eq!(stage[7], Some(MovableRatio::new_moved(Ratio::from(5))));
+    }
+
+    #[test]
+    fn generate_answer() {
+        let answer = AnswerInfo::generate::<6, GridCalculator<3, 2>>([1, 2, 3, 4, 5, 6].map(Ratio::from)).unwrap();
+        let stage = Stage::<6, GridCalculator<3, 2>>::from([1, 2, 3, 4, 5, 6]);
+        assert_eq!(answer.steps.verify(&stage, answer.ratio), Ok(answer.ratio));
+    }
+
+    fn stage() -> Stage<9, Grid3x3> {
+        Stage::from([
+            1, 2, 3,
+            4, 5, 6,
+            7, 8, 9,
+        ])
+    }
+}
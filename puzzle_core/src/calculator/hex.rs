@@ -0,0 +1,60 @@
+use crate::calculator::Calculator;
+use crate::topology::hex::HexTopology;
+
+/// The calculator for a hex board of `H` rows of `W` cells.
+///
+/// The stage size must be `W * H`, e.g. `Stage<9, HexCalculator<3, 3>>`.
+/// Cells move in the six directions of [`HEX_DIRECTIONS`](crate::topology::hex::HEX_DIRECTIONS).
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct HexCalculator<const W: usize, const H: usize>;
+
+impl<const W: usize, const H: usize> Calculator for HexCalculator<W, H> {
+    type Topology = HexTopology<W, H>;
+
+    #[inline]
+    fn topology(&self) -> &Self::Topology {
+        &HexTopology
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::calculator::hex::HexCalculator;
+    use crate::movable_ratio::MovableRatio;
+    use crate::move_dir::MoveDir;
+    use crate::ratio::Ratio;
+    use crate::stage::Stage;
+
+    #[test]
+    fn movable_dirs() {
+        let stage = stage();
+        assert_eq!(stage.movable_dirs(0), vec![MoveDir::Right, MoveDir::RightDown]);
+        assert_eq!(stage.movable_dirs(2), vec![MoveDir::LeftUp, MoveDir::RightUp, MoveDir::Right]);
+        assert_eq!(stage.movable_dirs(3), vec![MoveDir::LeftUp, MoveDir::Left]);
+    }
+
+    #[test]
+    fn add() {
+        let mut stage = stage();
+        stage.move_cell(2, MoveDir::LeftUp).unwrap();
+        assert_eq!(stage[2], None);
+        assert_eq!(stage[0], Some(MovableRatio::from(5)));
+    }
+
+    #[test]
+    fn swap_right() {
+        let mut stage = stage();
+        stage.move_cell(0, MoveDir::Right).unwrap();
+        assert_eq!(stage[0], Some(MovableRatio::from(2)));
+        assert_eq!(stage[1], Some(MovableRatio::new_moved(Ratio::from(1))));
+    }
+
+    // 1 2
+    //  4 5
+    fn stage() -> Stage<4, HexCalculator<2, 2>> {
+        Stage::from([
+            1, 2,
+            4, 5,
+        ])
+    }
+}
@@ -0,0 +1,261 @@
+//! Canonical forms of solutions, to tell which move sequences are really the same.
+//!
+//! A sequence of moves is reduced to the expressions it builds from the initial values.
+//! Swaps only move values around, so they disappear,
+//! independent moves build the same expressions in any order,
+//! and the operands of [`Operation::Add`], [`Operation::Mul`], [`Operation::Max`] and [`Operation::Min`]
+//! are flattened and sorted.
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+use crate::answer::steps::Steps;
+use crate::calculator::Calculator;
+use crate::number::PuzzleNumber;
+use crate::operation::Operation;
+use crate::ratio::Ratio;
+use crate::stage::Stage;
+
+/// An expression built by combining cells.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Expr<Num = Ratio> {
+    /// A value that was on the stage from the start.
+    Value(Num),
+    /// `operation` applied to `operands`.
+    ///
+    /// Non-commutative operations have exactly two operands, `md` then `ms`.
+    /// Commutative ones have two or more, sorted.
+    Combine(Operation, Vec<Expr<Num>>),
+}
+
+impl<Num: Ord> Expr<Num> {
+    /// Returns the expression of `md` combined with `ms`, in canonical form if both are.
+    pub fn combine(operation: Operation, md: Self, ms: Self) -> Self {
+        if !is_commutative(operation) {
+            return Expr::Combine(operation, vec![md, ms]);
+        }
+        let mut operands = Vec::new();
+        for operand in [md, ms] {
+            match operand {
+                Expr::Combine(op, inner) if op == operation => operands.extend(inner),
+                operand => operands.push(operand),
+            }
+        }
+        operands.sort();
+        Expr::Combine(operation, operands)
+    }
+}
+
+impl<Num: Ord> Ord for Expr<Num> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Expr::Value(lhs), Expr::Value(rhs)) => lhs.cmp(rhs),
+            (Expr::Value(_), Expr::Combine(..)) => Ordering::Less,
+            (Expr::Combine(..), Expr::Value(_)) => Ordering::Greater,
+            (Expr::Combine(lop, lhs), Expr::Combine(rop, rhs)) => lop.cmp(rop).then_with(|| lhs.cmp(rhs)),
+        }
+    }
+}
+
+impl<Num: Ord> PartialOrd for Expr<Num> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<Num: Display> Display for Expr<Num> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expr::Value(ratio) => write!(f, "{ratio}"),
+            Expr::Combine(operation @ (Operation::Max | Operation::Min), operands) => {
+                write!(f, "{}(", operation.symbol())?;
+                for (i, operand) in operands.iter().enumerate() {
+                    if 0 < i {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{operand}")?;
+                }
+                f.write_str(")")
+            }
+            Expr::Combine(operation, operands) => {
+                for (i, operand) in operands.iter().enumerate() {
+                    if 0 < i {
+                        write!(f, " {} ", operation.symbol())?;
+                    }
+                    match operand {
+                        Expr::Combine(op, _) if !matches!(op, Operation::Max | Operation::Min) => write!(f, "({operand})")?,
+                        operand => write!(f, "{operand}")?,
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The expressions left on a stage after a sequence of moves, sorted.
+///
+/// Two sequences played from the same stage are equivalent if they have the same canonical form.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct CanonicalForm<Num = Ratio>(pub Vec<Expr<Num>>);
+
+impl<Num: Display> Display for CanonicalForm<Num> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, expr) in self.0.iter().enumerate() {
+            if 0 < i {
+                f.write_str("; ")?;
+            }
+            write!(f, "{expr}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the canonical form of `steps` played from the current state of `stage`,
+/// or `None` if one of the steps can't be played.
+///
+/// Cells that already exist on `stage` count as plain values, even if they were combined earlier.
+pub fn canonical_form<const STAGE_SIZE: usize, Calc, Num>(
+    stage: &Stage<STAGE_SIZE, Calc, Num>,
+    steps: &Steps,
+) -> Option<CanonicalForm<Num>>
+    where Calc: Calculator + Clone + 'static,
+          Num: PuzzleNumber
+{
+    let mut stage = stage.clone();
+    let mut exprs = stage.ratios().map(|r| r.map(Expr::Value));
+    for (cell_no, dir) in steps {
+        stage.move_cell(*cell_no, *dir).ok()?;
+        let record = stage.history().last()?;
+        if record.result.is_some() {
+            let md = exprs[record.dist_no].take()?;
+            let ms = exprs[record.src_no].take()?;
+            exprs[record.dist_no] = Some(Expr::combine(record.operation, md, ms));
+        } else {
+            exprs.swap(record.src_no, record.dist_no);
+        }
+    }
+    let mut exprs = exprs.into_iter().flatten().collect::<Vec<_>>();
+    exprs.sort();
+    Some(CanonicalForm(exprs))
+}
+
+/// Returns whether `a` and `b` are the same solution written differently.
+///
+/// Sequences with a step that can't be played are never equivalent.
+pub fn equivalent<const STAGE_SIZE: usize, Calc, Num>(
+    stage: &Stage<STAGE_SIZE, Calc, Num>,
+    a: &Steps,
+    b: &Steps,
+) -> bool
+    where Calc: Calculator + Clone + 'static,
+          Num: PuzzleNumber
+{
+    match (canonical_form(stage, a), canonical_form(stage, b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => false
+    }
+}
+
+/// Keeps the first of each group of equivalent `solutions`, in order.
+///
+/// Solutions with a step that can't be played are dropped.
+pub fn distinct<const STAGE_SIZE: usize, Calc, Num>(
+    stage: &Stage<STAGE_SIZE, Calc, Num>,
+    solutions: impl IntoIterator<Item = Steps>,
+) -> Vec<Steps>
+    where Calc: Calculator + Clone + 'static,
+          Num: PuzzleNumber
+{
+    let mut forms = HashSet::new();
+    solutions
+        .into_iter()
+        .filter(|steps| canonical_form(stage, steps).is_some_and(|form| forms.insert(form)))
+        .collect()
+}
+
+const fn is_commutative(operation: Operation) -> bool {
+    matches!(operation, Operation::Add | Operation::Mul | Operation::Max | Operation::Min)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answer::steps::Steps;
+    use crate::calculator::small_size::SmallSizeCalculator;
+    use crate::canonical::{canonical_form, distinct, equivalent};
+    use crate::move_dir::MoveDir;
+    use crate::operation::Operation;
+    use crate::ratio::Ratio;
+    use crate::rule_set::RuleSet;
+    use crate::solver::solve;
+    use crate::stage::Stage;
+
+    #[test]
+    fn independent_moves_in_any_order() {
+        let stage = stage();
+        // 1 + 3 and 2 + 4 in either order
+        let a = Steps::from_iter([(2, MoveDir::LeftUp), (3, MoveDir::LeftUp), (1, MoveDir::LeftDown)]);
+        let b = Steps::from_iter([(3, MoveDir::LeftUp), (2, MoveDir::LeftUp), (1, MoveDir::LeftDown)]);
+        assert!(equivalent(&stage, &a, &b));
+        assert_eq!(canonical_form(&stage, &a).unwrap().to_string(), "(1 + 3) * (2 + 4)");
+    }
+
+    #[test]
+    fn commutative_operands() {
+        let stage = add_only();
+        // 2 + (1 + 3) and (1 + 2) + 3
+        let a = Steps::from_iter([(2, MoveDir::LeftUp), (0, MoveDir::RightUp)]);
+        let b = Steps::from_iter([(1, MoveDir::LeftDown), (2, MoveDir::LeftUp)]);
+        assert!(equivalent(&stage, &a, &b));
+        assert_eq!(canonical_form(&stage, &a).unwrap().to_string(), "4; 1 + 2 + 3");
+    }
+
+    #[test]
+    fn non_commutative_operands() {
+        let stage = stage();
+        // 2 - 1 and 4 - 3
+        let a = Steps::from_iter([(0, MoveDir::RightUp)]);
+        let b = Steps::from_iter([(2, MoveDir::RightUp)]);
+        assert!(!equivalent(&stage, &a, &b));
+        assert_eq!(canonical_form(&stage, &a).unwrap().to_string(), "3; 4; 2 - 1");
+    }
+
+    #[test]
+    fn swaps_disappear() {
+        let stage = add_only();
+        let a = Steps::from_iter([(1, MoveDir::LeftDown)]);
+        // 3 and 2 change places first
+        let b = Steps::from_iter([(2, MoveDir::Up), (2, MoveDir::LeftUp)]);
+        assert!(equivalent(&stage, &a, &b));
+        assert!(!equivalent(&stage, &a, &Steps::from_iter([(2, MoveDir::LeftUp), (2, MoveDir::LeftUp)])));
+    }
+
+    #[test]
+    fn distinct_solutions() {
+        let stage = stage();
+        let solutions = solve(&stage, Ratio::from(10));
+        let distinct = distinct(&stage, solutions.clone());
+        assert!(!distinct.is_empty());
+        assert!(distinct.len() < solutions.len());
+        for steps in &solutions {
+            assert!(distinct.iter().any(|d| equivalent(&stage, d, steps)));
+        }
+    }
+
+    /// Every diagonal adds.
+    fn add_only() -> Stage<4, SmallSizeCalculator> {
+        let rules = RuleSet::CLASSIC
+            .with(MoveDir::RightUp, Operation::Add)
+            .with(MoveDir::LeftDown, Operation::Add)
+            .with(MoveDir::RightDown, Operation::Add);
+        Stage::with_rules(SmallSizeCalculator, rules, [1, 2, 3, 4].map(Ratio::from))
+    }
+
+    fn stage() -> Stage<4, SmallSizeCalculator> {
+        Stage::from([
+            1, 2, 3, 4,
+        ])
+    }
+}
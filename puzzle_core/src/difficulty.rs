@@ -0,0 +1,228 @@
+//! Rates how hard a stage is from the structure of its solution space.
+
+use crate::answer::steps::Steps;
+use crate::calculator::Calculator;
+use crate::canonical::distinct;
+use crate::number::PuzzleNumber;
+use crate::operation::Operation;
+use crate::solver::explore_within;
+use crate::stage::Stage;
+
+/// The maximum number of states [`Difficulty::rate`] visits before giving up.
+pub const RATE_STATE_LIMIT: usize = 200_000;
+
+/// A coarse difficulty class derived from [`Difficulty::score`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Reflect))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DifficultyBand {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl DifficultyBand {
+    /// Returns the band the `score` falls into.
+    pub const fn from_score(score: u32) -> Self {
+        if score < 70 {
+            DifficultyBand::Easy
+        } else if score < 85 {
+            DifficultyBand::Medium
+        } else {
+            DifficultyBand::Hard
+        }
+    }
+
+    /// Returns the lowercase name of this band, such as `easy`.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            DifficultyBand::Easy => "easy",
+            DifficultyBand::Medium => "medium",
+            DifficultyBand::Hard => "hard",
+        }
+    }
+
+    /// The inverse of [`DifficultyBand::name`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        [DifficultyBand::Easy, DifficultyBand::Medium, DifficultyBand::Hard]
+            .into_iter()
+            .find(|band| band.name() == name)
+    }
+}
+
+/// The difficulty of reaching a goal from a stage.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Reflect))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Difficulty {
+    /// The number of distinct move sequences that reach the goal.
+    pub solutions: usize,
+    /// The number of solutions that aren't the same as another one written differently;
+    /// see [`crate::canonical`].
+    pub distinct_solutions: usize,
+    /// The number of steps of the shortest solution.
+    pub min_steps: usize,
+    /// The operations that every solution has to perform.
+    pub required_operations: Vec<Operation>,
+    /// Whether every solution passes through a fraction.
+    pub needs_fraction: bool,
+    /// Whether every solution passes through a negative number.
+    pub needs_negative: bool,
+    /// The number of branches that end without reaching the goal.
+    pub dead_ends: usize,
+    /// The overall score; higher is harder.
+    pub score: u32,
+}
+
+impl Difficulty {
+    /// Rates the difficulty of reaching `goal` from the current state of `stage`.
+    ///
+    /// Returns `None` if the goal can't be reached,
+    /// or if the solution space has more than [`RATE_STATE_LIMIT`] states.
+    pub fn rate<const STAGE_SIZE: usize, Calc, Num>(
+        stage: &Stage<STAGE_SIZE, Calc, Num>,
+        goal: Num,
+    ) -> Option<Self>
+        where Calc: Calculator + Clone + 'static,
+              Num: PuzzleNumber
+    {
+        let space = explore_within(stage, goal, RATE_STATE_LIMIT)?;
+        let traces = space
+            .solutions
+            .iter()
+            .map(|steps| trace(stage, steps))
+            .collect::<Vec<_>>();
+
+        let min_steps = traces.iter().map(|t| t.steps).min()?;
+        let required_operations = Operation::ALL
+            .into_iter()
+            .filter(|op| traces.iter().all(|t| t.operations.contains(op)))
+            .collect::<Vec<_>>();
+        let needs_fraction = traces.iter().all(|t| t.fraction);
+        let needs_negative = traces.iter().all(|t| t.negative);
+
+        let mut difficulty = Self {
+            solutions: space.solutions.len(),
+            distinct_solutions: distinct(stage, space.solutions.iter().cloned()).len(),
+            min_steps,
+            required_operations,
+            needs_fraction,
+            needs_negative,
+            dead_ends: space.dead_ends,
+            score: 0,
+        };
+        difficulty.score = difficulty.calc_score();
+        Some(difficulty)
+    }
+
+    #[inline]
+    pub const fn band(&self) -> DifficultyBand {
+        DifficultyBand::from_score(self.score)
+    }
+
+    fn calc_score(&self) -> u32 {
+        let length = self.min_steps as u32 * 5;
+        // The more dead ends per solution, the harder to find one.
+        let per_solution = self.dead_ends / self.solutions.max(1) + 1;
+        let scarcity = (per_solution.ilog2() * 4).min(50);
+        let operations = self
+            .required_operations
+            .iter()
+            .map(|op| match op {
+                Operation::Add | Operation::Max | Operation::Min => 0,
+                Operation::Sub | Operation::Swap | Operation::Concat => 4,
+                Operation::Mul => 6,
+                Operation::Rem | Operation::Pow => 8,
+                Operation::Div => 10,
+            })
+            .sum::<u32>();
+        let unique = if self.distinct_solutions == 1 { 10 } else { 0 };
+        let fraction = if self.needs_fraction { 15 } else { 0 };
+        let negative = if self.needs_negative { 10 } else { 0 };
+
+        length + scarcity + operations + unique + fraction + negative
+    }
+}
+
+struct Trace {
+    steps: usize,
+    operations: Vec<Operation>,
+    fraction: bool,
+    negative: bool,
+}
+
+fn trace<const STAGE_SIZE: usize, Calc, Num>(
+    stage: &Stage<STAGE_SIZE, Calc, Num>,
+    steps: &Steps,
+) -> Trace
+    where Calc: Calculator + Clone + 'static,
+          Num: PuzzleNumber
+{
+    let mut stage = stage.clone();
+    let mut trace = Trace {
+        steps: 0,
+        operations: Vec::new(),
+        fraction: false,
+        negative: false,
+    };
+    for (cell_no, dir) in steps {
+        if stage.move_cell(*cell_no, *dir).is_err() {
+            break;
+        }
+        trace.operations.extend(stage.history().last().map(|record| record.operation));
+        trace.steps += 1;
+        for ratio in stage.ratios().into_iter().flatten() {
+            trace.fraction |= ratio.is_fraction();
+            trace.negative |= ratio.is_negative();
+        }
+    }
+    trace
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroIsize;
+
+    use crate::calculator::small_size::SmallSizeCalculator;
+    use crate::difficulty::{Difficulty, DifficultyBand};
+    use crate::operation::Operation;
+    use crate::ratio::Ratio;
+    use crate::stage::Stage;
+
+    #[test]
+    fn unreachable() {
+        assert_eq!(Difficulty::rate(&stage(), Ratio::from(1000)), None);
+    }
+
+    #[test]
+    fn rate() {
+        let difficulty = Difficulty::rate(&stage(), Ratio::from(24)).unwrap();
+        assert!(0 < difficulty.distinct_solutions);
+        assert!(difficulty.distinct_solutions < difficulty.solutions);
+        assert!(3 <= difficulty.min_steps);
+        assert!(difficulty.required_operations.contains(&Operation::Mul));
+        assert!(!difficulty.needs_fraction);
+        assert!(!difficulty.needs_negative);
+    }
+
+    #[test]
+    fn needs_fraction_is_harder() {
+        let fraction = Difficulty::rate(&stage(), Ratio::new(1, NonZeroIsize::new(11).unwrap())).unwrap();
+        assert!(fraction.needs_fraction);
+        let integer = Difficulty::rate(&stage(), Ratio::from(10)).unwrap();
+        assert!(integer.score < fraction.score);
+    }
+
+    #[test]
+    fn band_order() {
+        assert_eq!(DifficultyBand::from_score(0), DifficultyBand::Easy);
+        assert_eq!(DifficultyBand::from_score(75), DifficultyBand::Medium);
+        assert_eq!(DifficultyBand::from_score(100), DifficultyBand::Hard);
+    }
+
+    fn stage() -> Stage<4, SmallSizeCalculator> {
+        Stage::from([
+            1, 2, 3, 4,
+        ])
+    }
+}
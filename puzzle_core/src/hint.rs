@@ -0,0 +1,109 @@
+//! Hints for a player who got stuck in the middle of a stage.
+
+use crate::calculator::Calculator;
+use crate::move_dir::MoveDir;
+use crate::number::PuzzleNumber;
+use crate::solver::shortest;
+use crate::stage::Stage;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Hint {
+    /// The goal can no longer be reached from the current state.
+    Unreachable,
+    /// Only the goal cell is left on the stage.
+    Reached,
+    /// Moving `cell_no` to `dir` is the first step of a shortest way to the goal,
+    /// which takes `remaining` steps including this one.
+    Next {
+        cell_no: usize,
+        dir: MoveDir,
+        remaining: usize,
+    },
+}
+
+impl Hint {
+    #[inline]
+    pub const fn is_reachable(&self) -> bool {
+        !matches!(self, Hint::Unreachable)
+    }
+
+    #[inline]
+    pub const fn next_move(&self) -> Option<(usize, MoveDir)> {
+        match self {
+            Hint::Next { cell_no, dir, .. } => Some((*cell_no, *dir)),
+            _ => None
+        }
+    }
+}
+
+/// Returns the best next move to reach `goal` from the current state of `stage`.
+///
+/// The state includes moved flags and empty cells,
+/// so this works even after the player has left the generated answer.
+pub fn hint<const STAGE_SIZE: usize, Calc, Num>(
+    stage: &Stage<STAGE_SIZE, Calc, Num>,
+    goal: Num,
+) -> Hint
+    where Calc: Calculator + Clone + 'static,
+          Num: PuzzleNumber
+{
+    let Some(steps) = shortest(stage, goal) else {
+        return Hint::Unreachable;
+    };
+    let remaining = steps.len();
+    match steps.get(0).copied() {
+        Some((cell_no, dir)) => Hint::Next {
+            cell_no,
+            dir,
+            remaining,
+        },
+        None => Hint::Reached
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::calculator::small_size::SmallSizeCalculator;
+    use crate::hint::{hint, Hint};
+    use crate::move_dir::MoveDir;
+    use crate::ratio::Ratio;
+    use crate::stage::Stage;
+
+    #[test]
+    fn follow_hints_to_goal() {
+        let mut stage = stage();
+        let goal = Ratio::from(10);
+        while let Some((cell_no, dir)) = hint(&stage, goal).next_move() {
+            stage.move_cell(cell_no, dir).unwrap();
+        }
+        assert_eq!(hint(&stage, goal), Hint::Reached);
+        assert_eq!(stage.last_ratio(), Some(goal));
+    }
+
+    #[test]
+    fn off_the_answer_path() {
+        let mut stage = stage();
+        // [4, 2, _, 4]
+        stage.move_cell(2, MoveDir::LeftUp).unwrap();
+        let Hint::Next { remaining, .. } = hint(&stage, Ratio::from(24)) else {
+            panic!("24 must be reachable");
+        };
+        assert!(0 < remaining);
+    }
+
+    #[test]
+    fn unreachable() {
+        let mut stage = stage();
+        // [4, 2, _, 4]
+        stage.move_cell(2, MoveDir::LeftUp).unwrap();
+        let hint = hint(&stage, Ratio::from(10));
+        assert_eq!(hint, Hint::Unreachable);
+        assert!(!hint.is_reachable());
+    }
+
+    fn stage() -> Stage<4, SmallSizeCalculator> {
+        Stage::from([
+            1, 2, 3, 4,
+        ])
+    }
+}
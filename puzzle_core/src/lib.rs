@@ -1,7 +1,17 @@
 pub mod ratio;
+pub mod number;
 pub mod movable_ratio;
 pub mod answer;
 pub mod move_dir;
+pub mod operation;
+pub mod rule_set;
 pub mod stage;
 pub mod calculator;
+pub mod topology;
+pub mod solver;
+pub mod difficulty;
+pub mod hint;
+pub mod notation;
+pub mod canonical;
+pub mod pack;
 
@@ -1,14 +1,15 @@
 use crate::ratio::Ratio;
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-pub struct MovableRatio {
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MovableRatio<Num = Ratio> {
     pub moved: bool,
-    pub ratio: Ratio,
+    pub ratio: Num,
 }
 
-impl MovableRatio {
+impl<Num> MovableRatio<Num> {
     #[cfg(test)]
-    pub(crate) fn new_moved(ratio: Ratio) -> Self {
+    pub(crate) fn new_moved(ratio: Num) -> Self {
         Self {
             moved: true,
             ratio,
@@ -16,9 +17,9 @@ impl MovableRatio {
     }
 }
 
-impl From<Ratio> for MovableRatio {
+impl<Num> From<Num> for MovableRatio<Num> {
     #[inline]
-    fn from(value: Ratio) -> Self {
+    fn from(value: Num) -> Self {
         Self {
             moved: false,
             ratio: value,
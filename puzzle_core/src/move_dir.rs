@@ -1,5 +1,8 @@
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "bevy", derive(bevy::prelude::Reflect))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MoveDir {
     LeftUp,
     Up,
@@ -12,11 +15,39 @@ pub enum MoveDir {
 }
 
 impl MoveDir {
-    #[inline]
-    pub const fn is_swap(&self) -> bool {
-        matches!(self, MoveDir::Up | MoveDir::Left | MoveDir::Right | MoveDir::Down)
+    /// All directions, in the order they are searched and displayed.
+    pub const ALL: [MoveDir; 8] = [
+        MoveDir::LeftUp,
+        MoveDir::Up,
+        MoveDir::RightUp,
+        MoveDir::Left,
+        MoveDir::Right,
+        MoveDir::LeftDown,
+        MoveDir::Down,
+        MoveDir::RightDown,
+    ];
+
+    /// Returns the short name of this direction used in [`crate::notation`], such as `LU`.
+    pub const fn abbreviation(&self) -> &'static str {
+        match self {
+            MoveDir::LeftUp => "LU",
+            MoveDir::Up => "U",
+            MoveDir::RightUp => "RU",
+            MoveDir::Left => "L",
+            MoveDir::Right => "R",
+            MoveDir::LeftDown => "LD",
+            MoveDir::Down => "D",
+            MoveDir::RightDown => "RD",
+        }
+    }
+
+    /// The inverse of [`MoveDir::abbreviation`].
+    pub fn from_abbreviation(abbreviation: &str) -> Option<MoveDir> {
+        MoveDir::ALL
+            .into_iter()
+            .find(|dir| dir.abbreviation() == abbreviation)
     }
-    
+
     pub const fn reverse(&self) -> MoveDir{
         match self {
             MoveDir::LeftUp => MoveDir::RightDown,
@@ -31,4 +62,17 @@ impl MoveDir {
     }
 }
 
-
+impl Display for MoveDir {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            MoveDir::LeftUp => "left up",
+            MoveDir::Up => "up",
+            MoveDir::RightUp => "right up",
+            MoveDir::Left => "left",
+            MoveDir::Right => "right",
+            MoveDir::LeftDown => "left down",
+            MoveDir::Down => "down",
+            MoveDir::RightDown => "right down",
+        })
+    }
+}
@@ -0,0 +1,247 @@
+//! A text format for sharing a puzzle and its solution, in the spirit of PGN for chess.
+//!
+//! ```text
+//! # Headers come first; `Board`, `Cells` and `Goal` are required.
+//! [Board "diamond"]
+//! [Cells "1 3 9 4"]
+//! [Goal "4/5"]
+//! [Seed "42"]
+//! [Author "someone"]
+//! [Difficulty "hard"]
+//!
+//! { Comments in braces can appear anywhere and span lines. }
+//! 2 U; 0 R; 0 R; 2 LU; 0 RU; 1 RD
+//! ```
+//!
+//! Each move is a cell number followed by [`MoveDir::abbreviation`](crate::move_dir::MoveDir::abbreviation),
+//! and moves are separated by `;`.
+//! `#` starts a comment that runs to the end of the line.
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::answer::steps::Steps;
+use crate::calculator::Calculator;
+use crate::difficulty::DifficultyBand;
+use crate::notation::parser::ParseError;
+use crate::ratio::Ratio;
+use crate::stage::{RatioArray, Stage};
+use crate::topology::Topology;
+
+pub mod parser;
+
+/// A puzzle, its solution and metadata, as written in the notation.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Notation {
+    /// The [`Topology::name`] of the board.
+    pub board: String,
+    /// The initial value of each cell.
+    pub cells: Vec<Ratio>,
+    pub goal: Ratio,
+    pub seed: Option<u64>,
+    pub author: Option<String>,
+    pub difficulty: Option<DifficultyBand>,
+    /// The moves from the initial state; may be empty.
+    pub steps: Steps,
+}
+
+/// The error returned when a [`Notation`] doesn't describe the requested stage.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum MismatchError {
+    Board {
+        expected: String,
+        actual: String,
+    },
+    CellCount {
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl Display for MismatchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MismatchError::Board { expected, actual } => write!(f, "expected a {expected} board, but got {actual}"),
+            MismatchError::CellCount { expected, actual } => write!(f, "expected {expected} cells, but got {actual}"),
+        }
+    }
+}
+
+impl std::error::Error for MismatchError {}
+
+impl Notation {
+    /// Creates a notation without metadata.
+    pub fn new(topology: &impl Topology, cells: &[Ratio], goal: Ratio, steps: Steps) -> Self {
+        Self {
+            board: topology.name(),
+            cells: cells.to_vec(),
+            goal,
+            seed: None,
+            author: None,
+            difficulty: None,
+            steps,
+        }
+    }
+
+    /// Creates the initial stage of this puzzle.
+    ///
+    /// Fails if the board name or the number of cells doesn't match `calculator`'s topology.
+    pub fn to_stage<const STAGE_SIZE: usize, Calc>(&self, calculator: Calc) -> Result<Stage<STAGE_SIZE, Calc>, MismatchError>
+        where Calc: Calculator + 'static
+    {
+        let board = calculator.topology().name();
+        if board != self.board {
+            return Err(MismatchError::Board {
+                expected: board,
+                actual: self.board.clone(),
+            });
+        }
+        let ratios: RatioArray<STAGE_SIZE> = self.cells.as_slice().try_into().map_err(|_| MismatchError::CellCount {
+            expected: STAGE_SIZE,
+            actual: self.cells.len(),
+        })?;
+        Ok(Stage::new(calculator, ratios))
+    }
+}
+
+impl Display for Notation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_header(f, "Board", &self.board)?;
+        let cells = self.cells
+            .iter()
+            .map(Ratio::to_string)
+            .collect::<Vec<_>>()
+            .join(" ");
+        write_header(f, "Cells", &cells)?;
+        write_header(f, "Goal", &self.goal.to_string())?;
+        if let Some(seed) = self.seed {
+            write_header(f, "Seed", &seed.to_string())?;
+        }
+        if let Some(author) = &self.author {
+            write_header(f, "Author", author)?;
+        }
+        if let Some(difficulty) = self.difficulty {
+            write_header(f, "Difficulty", difficulty.name())?;
+        }
+        if !self.steps.is_empty() {
+            write!(f, "\n{}\n", format_moves(&self.steps))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Notation {
+    type Err = ParseError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parser::parse(s)
+    }
+}
+
+/// Writes `steps` as a move list such as `2 LU; 0 R`.
+pub fn format_moves(steps: &Steps) -> String {
+    steps
+        .iter()
+        .map(|(cell_no, dir)| format!("{cell_no} {}", dir.abbreviation()))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn write_header(f: &mut Formatter<'_>, name: &str, value: &str) -> std::fmt::Result {
+    let value = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r");
+    writeln!(f, "[{name} \"{value}\"]")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroIsize;
+
+    use crate::answer::generate_stage_from_seed;
+    use crate::calculator::grid::GridCalculator;
+    use crate::calculator::small_size::SmallSizeCalculator;
+    use crate::difficulty::DifficultyBand;
+    use crate::move_dir::MoveDir;
+    use crate::notation::{MismatchError, Notation};
+    use crate::ratio::Ratio;
+    use crate::stage::Stage;
+    use crate::topology::diamond::DiamondTopology;
+
+    const SAMPLE: &str = r#"# A sample from the README.
+[Board "diamond"]
+[Cells "1 3 9 4"]
+[Goal "4/5"]
+[Seed "42"]
+[Author "someone \"quoted\""]
+[Difficulty "hard"]
+
+{ swap first,
+  then combine }
+2 U; 0 R; 0 R; 2 LU; 0 RU; 1 RD
+"#;
+
+    #[test]
+    fn parse() {
+        let notation = SAMPLE.parse::<Notation>().unwrap();
+        assert_eq!(notation.board, "diamond");
+        assert_eq!(notation.cells, [1, 3, 9, 4].map(Ratio::from));
+        assert_eq!(notation.goal, Ratio::new(4, NonZeroIsize::new(5).unwrap()));
+        assert_eq!(notation.seed, Some(42));
+        assert_eq!(notation.author.as_deref(), Some("someone \"quoted\""));
+        assert_eq!(notation.difficulty, Some(DifficultyBand::Hard));
+        assert_eq!(notation.steps.len(), 6);
+    }
+
+    #[test]
+    fn round_trip() {
+        let notation = SAMPLE.parse::<Notation>().unwrap();
+        assert_eq!(notation.to_string().parse::<Notation>().unwrap(), notation);
+    }
+
+    #[test]
+    fn round_trip_multi_line_header() {
+        let mut notation = SAMPLE.parse::<Notation>().unwrap();
+        notation.author = Some("first line\r\nsecond \\n line".to_string());
+        assert_eq!(notation.to_string().parse::<Notation>().unwrap(), notation);
+    }
+
+    #[test]
+    fn round_trip_generated_stage() {
+        let (ratios, answer) = generate_stage_from_seed::<4, SmallSizeCalculator>(42).unwrap();
+        let mut notation = Notation::new(&DiamondTopology, &ratios, answer.ratio, answer.steps.clone());
+        notation.seed = Some(42);
+
+        let parsed = notation.to_string().parse::<Notation>().unwrap();
+        assert_eq!(parsed.steps, answer.steps);
+        let mut stage: Stage<4, SmallSizeCalculator> = parsed.to_stage(SmallSizeCalculator).unwrap();
+        assert_eq!(stage.ratios(), ratios.map(Some));
+        for (cell_no, dir) in parsed.steps.iter() {
+            stage.move_cell(*cell_no, *dir).unwrap();
+        }
+        assert_eq!(stage.last_ratio(), Some(parsed.goal));
+    }
+
+    #[test]
+    fn print() {
+        let steps = [(2, MoveDir::LeftUp), (0, MoveDir::Right)].into_iter().collect();
+        let notation = Notation::new(&DiamondTopology, &[1, 2, 3, 4].map(Ratio::from), Ratio::from(4), steps);
+        assert_eq!(notation.to_string(), "[Board \"diamond\"]\n[Cells \"1 2 3 4\"]\n[Goal \"4\"]\n\n2 LU; 0 R\n");
+    }
+
+    #[test]
+    fn mismatch() {
+        let notation = SAMPLE.parse::<Notation>().unwrap();
+        assert_eq!(
+            notation.to_stage::<6, GridCalculator<3, 2>>(GridCalculator).err(),
+            Some(MismatchError::Board {
+                expected: String::from("grid 3x2"),
+                actual: String::from("diamond"),
+            })
+        );
+    }
+}
@@ -0,0 +1,436 @@
+//! Parses the text written by the [`Display`] implementation of [`Notation`].
+
+use std::fmt::{Display, Formatter};
+use std::iter::Peekable;
+use std::str::Chars;
+
+use crate::answer::steps::Steps;
+use crate::difficulty::DifficultyBand;
+use crate::move_dir::MoveDir;
+use crate::notation::Notation;
+use crate::ratio::Ratio;
+use crate::ratio::parse::ParseRatioError;
+
+/// The error returned when the text isn't valid notation.
+///
+/// `line` and `column` are 1-based and point at the offending character.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ParseErrorKind {
+    /// Something other than what the grammar allows here, such as a missing `]`.
+    Expected(&'static str),
+    UnterminatedComment,
+    UnterminatedString,
+    UnknownHeader(String),
+    DuplicateHeader(String),
+    MissingHeader(&'static str),
+    HeaderAfterMoves,
+    InvalidRatio {
+        value: String,
+        error: ParseRatioError,
+    },
+    InvalidSeed(String),
+    InvalidDifficulty(String),
+    InvalidCellNo(String),
+    InvalidDirection(String),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.kind)
+    }
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::Expected(expected) => write!(f, "expected {expected}"),
+            ParseErrorKind::UnterminatedComment => f.write_str("the comment is not closed"),
+            ParseErrorKind::UnterminatedString => f.write_str("the string is not closed"),
+            ParseErrorKind::UnknownHeader(name) => write!(f, "unknown header `{name}`"),
+            ParseErrorKind::DuplicateHeader(name) => write!(f, "duplicate header `{name}`"),
+            ParseErrorKind::MissingHeader(name) => write!(f, "missing header `{name}`"),
+            ParseErrorKind::HeaderAfterMoves => f.write_str("headers must come before the moves"),
+            ParseErrorKind::InvalidRatio { value, error } => write!(f, "`{value}` is not a number: {error}"),
+            ParseErrorKind::InvalidSeed(value) => write!(f, "`{value}` is not a seed"),
+            ParseErrorKind::InvalidDifficulty(value) => write!(f, "`{value}` is not a difficulty"),
+            ParseErrorKind::InvalidCellNo(value) => write!(f, "`{value}` is not a cell number"),
+            ParseErrorKind::InvalidDirection(value) => write!(f, "`{value}` is not a direction"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub(crate) fn parse(text: &str) -> Result<Notation, ParseError> {
+    let (tokens, end) = tokenize(text)?;
+    Parser {
+        tokens: tokens.into_iter().peekable(),
+        end,
+    }.parse()
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum TokenKind {
+    LBracket,
+    RBracket,
+    Semicolon,
+    Str(String),
+    Word(String),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    pos: Pos,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Pos {
+    line: usize,
+    column: usize,
+}
+
+impl Pos {
+    #[inline]
+    const fn error(self, kind: ParseErrorKind) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            kind,
+        }
+    }
+
+    /// Returns the position `chars` characters to the right.
+    #[inline]
+    const fn offset(self, chars: usize) -> Pos {
+        Pos {
+            line: self.line,
+            column: self.column + chars,
+        }
+    }
+}
+
+struct Lexer<'a> {
+    chars: Peekable<Chars<'a>>,
+    pos: Pos,
+}
+
+impl Lexer<'_> {
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.pos.line += 1;
+            self.pos.column = 1;
+        } else {
+            self.pos.column += 1;
+        }
+        Some(c)
+    }
+}
+
+/// Splits `text` into tokens, dropping whitespace and comments.
+/// Also returns the position of the end of the text.
+fn tokenize(text: &str) -> Result<(Vec<Token>, Pos), ParseError> {
+    let mut lexer = Lexer {
+        chars: text.chars().peekable(),
+        pos: Pos { line: 1, column: 1 },
+    };
+    let mut tokens = Vec::new();
+    while let Some(&c) = lexer.chars.peek() {
+        let pos = lexer.pos;
+        let kind = match c {
+            c if c.is_whitespace() => {
+                lexer.next();
+                continue;
+            }
+            '#' => {
+                while lexer.next().is_some_and(|c| c != '\n') {}
+                continue;
+            }
+            '{' => {
+                while lexer.next().ok_or(pos.error(ParseErrorKind::UnterminatedComment))? != '}' {}
+                continue;
+            }
+            '}' => {
+                return Err(pos.error(ParseErrorKind::Expected("`{` before `}`")));
+            }
+            '[' | ']' | ';' => {
+                lexer.next();
+                match c {
+                    '[' => TokenKind::LBracket,
+                    ']' => TokenKind::RBracket,
+                    _ => TokenKind::Semicolon,
+                }
+            }
+            '"' => {
+                lexer.next();
+                TokenKind::Str(read_string(&mut lexer).ok_or(pos.error(ParseErrorKind::UnterminatedString))?)
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&c) = lexer.chars.peek() {
+                    if c.is_whitespace() || "[]{};#\"".contains(c) {
+                        break;
+                    }
+                    word.push(c);
+                    lexer.next();
+                }
+                TokenKind::Word(word)
+            }
+        };
+        tokens.push(Token { kind, pos });
+    }
+    Ok((tokens, lexer.pos))
+}
+
+/// Reads the rest of a string after the opening quote; `\"`, `\\`, `\n` and `\r` are escapes.
+/// Returns `None` if the line ends before the closing quote.
+fn read_string(lexer: &mut Lexer) -> Option<String> {
+    let mut value = String::new();
+    loop {
+        match lexer.next()? {
+            '"' => return Some(value),
+            '\n' => return None,
+            '\\' => value.push(match lexer.next()? {
+                'n' => '\n',
+                'r' => '\r',
+                c => c,
+            }),
+            c => value.push(c),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Headers {
+    board: Option<String>,
+    cells: Option<Vec<Ratio>>,
+    goal: Option<Ratio>,
+    seed: Option<u64>,
+    author: Option<String>,
+    difficulty: Option<DifficultyBand>,
+}
+
+struct Parser {
+    tokens: Peekable<std::vec::IntoIter<Token>>,
+    end: Pos,
+}
+
+impl Parser {
+    fn parse(mut self) -> Result<Notation, ParseError> {
+        let mut headers = Headers::default();
+        while self.tokens.peek().is_some_and(|t| t.kind == TokenKind::LBracket) {
+            self.tokens.next();
+            self.parse_header(&mut headers)?;
+        }
+
+        let pos = self.tokens.peek().map(|t| t.pos).unwrap_or(self.end);
+        let missing = |name| pos.error(ParseErrorKind::MissingHeader(name));
+        Ok(Notation {
+            board: headers.board.ok_or_else(|| missing("Board"))?,
+            cells: headers.cells.ok_or_else(|| missing("Cells"))?,
+            goal: headers.goal.ok_or_else(|| missing("Goal"))?,
+            seed: headers.seed,
+            author: headers.author,
+            difficulty: headers.difficulty,
+            steps: self.parse_moves()?,
+        })
+    }
+
+    /// Parses `Name "value"]` after the opening bracket.
+    fn parse_header(&mut self, headers: &mut Headers) -> Result<(), ParseError> {
+        let (name, name_pos) = match self.tokens.next() {
+            Some(Token { kind: TokenKind::Word(name), pos }) => (name, pos),
+            token => return Err(self.unexpected(token, "a header name")),
+        };
+        let (value, value_pos) = match self.tokens.next() {
+            Some(Token { kind: TokenKind::Str(value), pos }) => (value, pos.offset(1)),
+            token => return Err(self.unexpected(token, "a quoted header value")),
+        };
+        match self.tokens.next() {
+            Some(Token { kind: TokenKind::RBracket, .. }) => {}
+            token => return Err(self.unexpected(token, "`]`")),
+        }
+
+        let duplicate = match name.as_str() {
+            "Board" => headers.board.replace(value).is_some(),
+            "Cells" => headers.cells.replace(parse_cells(&value, value_pos)?).is_some(),
+            "Goal" => {
+                let goal = value.parse().map_err(|error| value_pos.error(ParseErrorKind::InvalidRatio {
+                    value: value.clone(),
+                    error,
+                }))?;
+                headers.goal.replace(goal).is_some()
+            }
+            "Seed" => {
+                let seed = value.trim().parse().map_err(|_| value_pos.error(ParseErrorKind::InvalidSeed(value.clone())))?;
+                headers.seed.replace(seed).is_some()
+            }
+            "Author" => headers.author.replace(value).is_some(),
+            "Difficulty" => {
+                let band = DifficultyBand::from_name(value.trim()).ok_or(value_pos.error(ParseErrorKind::InvalidDifficulty(value.clone())))?;
+                headers.difficulty.replace(band).is_some()
+            }
+            _ => return Err(name_pos.error(ParseErrorKind::UnknownHeader(name))),
+        };
+        if duplicate {
+            return Err(name_pos.error(ParseErrorKind::DuplicateHeader(name)));
+        }
+        Ok(())
+    }
+
+    fn parse_moves(&mut self) -> Result<Steps, ParseError> {
+        let mut steps = Steps::default();
+        while let Some(token) = self.tokens.next() {
+            let cell_no = match token.kind {
+                TokenKind::Word(word) => word.parse().map_err(|_| token.pos.error(ParseErrorKind::InvalidCellNo(word)))?,
+                TokenKind::LBracket => return Err(token.pos.error(ParseErrorKind::HeaderAfterMoves)),
+                kind => return Err(self.unexpected(Some(Token { kind, pos: token.pos }), "a cell number")),
+            };
+            let dir = match self.tokens.next() {
+                Some(Token { kind: TokenKind::Word(word), pos }) => {
+                    MoveDir::from_abbreviation(&word).ok_or(pos.error(ParseErrorKind::InvalidDirection(word)))?
+                }
+                token => return Err(self.unexpected(token, "a direction")),
+            };
+            steps.push(cell_no, dir);
+
+            match self.tokens.next() {
+                None | Some(Token { kind: TokenKind::Semicolon, .. }) => {}
+                Some(Token { kind: TokenKind::LBracket, pos }) => return Err(pos.error(ParseErrorKind::HeaderAfterMoves)),
+                token => return Err(self.unexpected(token, "`;`")),
+            }
+        }
+        Ok(steps)
+    }
+
+    #[inline]
+    fn unexpected(&self, token: Option<Token>, expected: &'static str) -> ParseError {
+        token
+            .map(|t| t.pos)
+            .unwrap_or(self.end)
+            .error(ParseErrorKind::Expected(expected))
+    }
+}
+
+/// Parses whitespace separated ratios; `value_pos` is the position of the first character of `value`.
+fn parse_cells(value: &str, value_pos: Pos) -> Result<Vec<Ratio>, ParseError> {
+    let mut cells = Vec::new();
+    let mut word_start = None;
+    for (i, (byte, c)) in value.char_indices().chain([(value.len(), ' ')]).enumerate() {
+        match (word_start, c.is_whitespace()) {
+            (None, false) => word_start = Some((i, byte)),
+            (Some((column, start)), true) => {
+                let word = &value[start..byte];
+                let ratio = word.parse().map_err(|error| value_pos.offset(column).error(ParseErrorKind::InvalidRatio {
+                    value: word.to_string(),
+                    error,
+                }))?;
+                cells.push(ratio);
+                word_start = None;
+            }
+            _ => {}
+        }
+    }
+    Ok(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::notation::Notation;
+    use crate::notation::parser::{ParseError, ParseErrorKind};
+    use crate::ratio::parse::ParseRatioError;
+
+    const HEADERS: &str = "[Board \"diamond\"]\n[Cells \"1 2 3 4\"]\n[Goal \"10\"]\n";
+
+    #[test]
+    fn no_moves() {
+        let notation = HEADERS.parse::<Notation>().unwrap();
+        assert!(notation.steps.is_empty());
+    }
+
+    #[test]
+    fn trailing_semicolon_and_comments() {
+        let notation = format!("{HEADERS}2 LU; # add\n0 R;").parse::<Notation>().unwrap();
+        assert_eq!(notation.steps.len(), 2);
+    }
+
+    #[test]
+    fn invalid_direction() {
+        assert_eq!(
+            format!("{HEADERS}\n2 LU; 0 X").parse::<Notation>(),
+            Err(ParseError { line: 5, column: 9, kind: ParseErrorKind::InvalidDirection(String::from("X")) })
+        );
+    }
+
+    #[test]
+    fn invalid_cell() {
+        assert_eq!(
+            "[Board \"diamond\"]\n[Cells \"1 2 x 4\"]".parse::<Notation>(),
+            Err(ParseError {
+                line: 2,
+                column: 13,
+                kind: ParseErrorKind::InvalidRatio {
+                    value: String::from("x"),
+                    error: ParseRatioError::InvalidDigit,
+                },
+            })
+        );
+        assert_eq!(
+            "[Board \"diamond\"]\n[Cells \"1 2 1/0 4\"]".parse::<Notation>().map_err(|e| e.kind),
+            Err(ParseErrorKind::InvalidRatio {
+                value: String::from("1/0"),
+                error: ParseRatioError::ZeroDenominator,
+            })
+        );
+    }
+
+    #[test]
+    fn missing_header() {
+        assert_eq!(
+            "[Board \"diamond\"]\n[Goal \"10\"]\n0 R".parse::<Notation>(),
+            Err(ParseError { line: 3, column: 1, kind: ParseErrorKind::MissingHeader("Cells") })
+        );
+    }
+
+    #[test]
+    fn missing_separator() {
+        assert_eq!(
+            format!("{HEADERS}2 LU 0 R").parse::<Notation>(),
+            Err(ParseError { line: 4, column: 6, kind: ParseErrorKind::Expected("`;`") })
+        );
+    }
+
+    #[test]
+    fn unterminated() {
+        assert_eq!(
+            format!("{HEADERS}{{ comment").parse::<Notation>(),
+            Err(ParseError { line: 4, column: 1, kind: ParseErrorKind::UnterminatedComment })
+        );
+        assert_eq!(
+            "[Board \"diamond]".parse::<Notation>(),
+            Err(ParseError { line: 1, column: 8, kind: ParseErrorKind::UnterminatedString })
+        );
+    }
+
+    #[test]
+    fn header_after_moves() {
+        assert_eq!(
+            format!("{HEADERS}2 LU\n[Seed \"1\"]").parse::<Notation>().map_err(|e| e.kind),
+            Err(ParseErrorKind::HeaderAfterMoves)
+        );
+    }
+
+    #[test]
+    fn display_error() {
+        let error = format!("{HEADERS}2 UP").parse::<Notation>().unwrap_err();
+        assert_eq!(error.to_string(), "line 4, column 3: `UP` is not a direction");
+    }
+}
@@ -0,0 +1,116 @@
+//! The values cells hold, as a trait so the engine can run on other kinds of numbers than [`Ratio`].
+//!
+//! [`Ratio`] is the default everywhere; the other implementations are
+//! rationals of wider integers in [`rational`], integers modulo a prime in [`modular`]
+//! and plain integers that only divide exactly, implemented on `isize`.
+
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+
+use crate::ratio::format::RatioFormat;
+use crate::ratio::{ArithmeticError, Ratio};
+
+pub mod integer;
+pub mod modular;
+pub mod rational;
+
+/// A value a cell can hold.
+///
+/// Every operation returns an [`ArithmeticError`] instead of panicking,
+/// so a move that can't be calculated is simply not allowed.
+/// The order is used by [`crate::operation::Operation::Max`] and [`crate::operation::Operation::Min`],
+/// and to sort [`crate::canonical`] forms.
+pub trait PuzzleNumber: Clone + Eq + Ord + Hash + Debug + Display + Send + Sync + 'static {
+    fn from_integer(value: isize) -> Self;
+
+    /// Returns the value as an integer if it is one and fits in `isize`.
+    fn to_integer(&self) -> Option<isize>;
+
+    fn checked_add(&self, rhs: &Self) -> Result<Self, ArithmeticError>;
+
+    fn checked_sub(&self, rhs: &Self) -> Result<Self, ArithmeticError>;
+
+    fn checked_mul(&self, rhs: &Self) -> Result<Self, ArithmeticError>;
+
+    fn checked_div(&self, rhs: &Self) -> Result<Self, ArithmeticError>;
+
+    /// Calculates the remainder of the floored division, so the result has the sign of `rhs`.
+    fn checked_rem(&self, rhs: &Self) -> Result<Self, ArithmeticError>;
+
+    /// Raises `self` to the power of `exp`; a negative `exp` takes the reciprocal.
+    fn checked_pow(&self, exp: isize) -> Result<Self, ArithmeticError>;
+
+    /// Returns whether the value has a fractional part, which makes a stage harder to solve.
+    #[inline]
+    fn is_fraction(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn is_negative(&self) -> bool {
+        *self < Self::from_integer(0)
+    }
+
+    /// Writes the value in `format`; values that aren't fractions may write it the same in every format.
+    #[inline]
+    fn display_with(&self, _format: RatioFormat) -> String {
+        self.to_string()
+    }
+}
+
+impl PuzzleNumber for Ratio {
+    #[inline]
+    fn from_integer(value: isize) -> Self {
+        Ratio::from(value)
+    }
+
+    #[inline]
+    fn to_integer(&self) -> Option<isize> {
+        Ratio::to_integer(self)
+    }
+
+    #[inline]
+    fn checked_add(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        Ratio::checked_add(*self, *rhs)
+    }
+
+    #[inline]
+    fn checked_sub(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        Ratio::checked_sub(*self, *rhs)
+    }
+
+    #[inline]
+    fn checked_mul(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        Ratio::checked_mul(*self, *rhs)
+    }
+
+    #[inline]
+    fn checked_div(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        Ratio::checked_div(*self, *rhs)
+    }
+
+    #[inline]
+    fn checked_rem(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        Ratio::checked_rem(*self, *rhs)
+    }
+
+    #[inline]
+    fn checked_pow(&self, exp: isize) -> Result<Self, ArithmeticError> {
+        Ratio::checked_pow(*self, exp)
+    }
+
+    #[inline]
+    fn is_fraction(&self) -> bool {
+        self.denom != 1
+    }
+
+    #[inline]
+    fn is_negative(&self) -> bool {
+        self.numer < 0
+    }
+
+    #[inline]
+    fn display_with(&self, format: RatioFormat) -> String {
+        self.display(format).to_string()
+    }
+}
@@ -0,0 +1,104 @@
+//! Plain integers as cell values: a division is only allowed if it is exact.
+
+use crate::number::PuzzleNumber;
+use crate::ratio::ArithmeticError;
+
+impl PuzzleNumber for isize {
+    #[inline]
+    fn from_integer(value: isize) -> Self {
+        value
+    }
+
+    #[inline]
+    fn to_integer(&self) -> Option<isize> {
+        Some(*self)
+    }
+
+    #[inline]
+    fn checked_add(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        isize::checked_add(*self, *rhs).ok_or(ArithmeticError::Overflow)
+    }
+
+    #[inline]
+    fn checked_sub(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        isize::checked_sub(*self, *rhs).ok_or(ArithmeticError::Overflow)
+    }
+
+    #[inline]
+    fn checked_mul(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        isize::checked_mul(*self, *rhs).ok_or(ArithmeticError::Overflow)
+    }
+
+    /// Fails with [`ArithmeticError::Undefined`] if `rhs` doesn't divide `self`.
+    fn checked_div(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        if *rhs == 0 {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        if PuzzleNumber::checked_rem(self, rhs)? != 0 {
+            return Err(ArithmeticError::Undefined);
+        }
+        isize::checked_div(*self, *rhs).ok_or(ArithmeticError::Overflow)
+    }
+
+    fn checked_rem(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        if *rhs == 0 {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        let rem = isize::checked_rem(*self, *rhs).ok_or(ArithmeticError::Overflow)?;
+        Ok(if rem != 0 && (rem < 0) != (*rhs < 0) { rem + rhs } else { rem })
+    }
+
+    /// A negative `exp` is only allowed on `1` and `-1`, the only integers whose reciprocals are integers.
+    fn checked_pow(&self, exp: isize) -> Result<Self, ArithmeticError> {
+        if exp < 0 {
+            return match self {
+                0 => Err(ArithmeticError::DivisionByZero),
+                1 => Ok(1),
+                -1 => Ok(if exp % 2 == 0 { 1 } else { -1 }),
+                _ => Err(ArithmeticError::Undefined),
+            };
+        }
+        let exp = u32::try_from(exp).map_err(|_| ArithmeticError::Overflow)?;
+        isize::checked_pow(*self, exp).ok_or(ArithmeticError::Overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::calculator::small_size::SmallSizeCalculator;
+    use crate::move_dir::MoveDir;
+    use crate::number::PuzzleNumber;
+    use crate::ratio::ArithmeticError;
+    use crate::stage::outcome::MoveError;
+    use crate::stage::Stage;
+
+    #[test]
+    fn exact_division() {
+        assert_eq!(PuzzleNumber::checked_div(&6isize, &3), Ok(2));
+        assert_eq!(PuzzleNumber::checked_div(&6isize, &4), Err(ArithmeticError::Undefined));
+        assert_eq!(PuzzleNumber::checked_div(&6isize, &0), Err(ArithmeticError::DivisionByZero));
+        assert_eq!(PuzzleNumber::checked_div(&isize::MIN, &-1), Err(ArithmeticError::Overflow));
+    }
+
+    #[test]
+    fn floored_rem() {
+        assert_eq!(PuzzleNumber::checked_rem(&-7isize, &3), Ok(2));
+        assert_eq!(PuzzleNumber::checked_rem(&7isize, &-3), Ok(-2));
+    }
+
+    #[test]
+    fn pow() {
+        assert_eq!(PuzzleNumber::checked_pow(&-1isize, -3), Ok(-1));
+        assert_eq!(PuzzleNumber::checked_pow(&2isize, -1), Err(ArithmeticError::Undefined));
+        assert_eq!(PuzzleNumber::checked_pow(&2isize, 10), Ok(1024));
+    }
+
+    #[test]
+    fn stage() {
+        // Moving down right divides; 4 / 3 isn't exact, but 2 / 1 is.
+        let mut stage = Stage::<4, SmallSizeCalculator, isize>::new(SmallSizeCalculator, [1, 3, 2, 4]);
+        assert_eq!(stage.move_cell(1, MoveDir::RightDown), Err(MoveError::Undefined));
+        assert!(stage.move_cell(0, MoveDir::RightDown).is_ok());
+        assert_eq!(stage.ratios(), [None, Some(3), Some(2), Some(4)]);
+    }
+}
@@ -0,0 +1,232 @@
+//! Integers modulo a prime as cell values, for a clock arithmetic variant.
+
+use std::fmt::{Display, Formatter};
+
+use crate::number::PuzzleNumber;
+use crate::ratio::ArithmeticError;
+
+/// An integer modulo `P`, kept between `0` and `P - 1`.
+///
+/// Every value but zero can be divided by if `P` is a prime.
+/// With another modulus, dividing by a value that shares a factor with `P` is [`ArithmeticError::Undefined`].
+/// The order is the order of the representatives, so `Max` and `Min` compare them from `0` to `P - 1`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Modular<const P: u64>(u64);
+
+impl<const P: u64> Modular<P> {
+    /// Returns `value` modulo `P`.
+    #[inline]
+    pub const fn new(value: i128) -> Self {
+        const { assert!(0 < P, "the modulus must be positive") };
+        Self(value.rem_euclid(P as i128) as u64)
+    }
+
+    /// Returns the representative between `0` and `P - 1`.
+    #[inline]
+    pub const fn value(self) -> u64 {
+        self.0
+    }
+
+    /// Returns the value that gives `1` when multiplied by this one.
+    pub fn inverse(self) -> Result<Self, ArithmeticError> {
+        if self.0 == 0 {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        // The extended Euclidean algorithm, keeping only the coefficients of `self`.
+        let (mut r0, mut r1) = (P as i128, self.0 as i128);
+        let (mut t0, mut t1) = (0i128, 1i128);
+        while r1 != 0 {
+            let q = r0 / r1;
+            (r0, r1) = (r1, r0 - q * r1);
+            (t0, t1) = (t1, t0 - q * t1);
+        }
+        if r0 == 1 {
+            Ok(Self::new(t0))
+        } else {
+            Err(ArithmeticError::Undefined)
+        }
+    }
+
+    fn pow_unsigned(self, mut exp: usize) -> Self {
+        let (mut base, mut result) = (self, Self::new(1));
+        while 0 < exp {
+            if exp & 1 == 1 {
+                result = result.mul(base);
+            }
+            base = base.mul(base);
+            exp >>= 1;
+        }
+        result
+    }
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        Self::new((self.0 as u128 * rhs.0 as u128 % P as u128) as i128)
+    }
+}
+
+/// Deserializes the representative, so a value that isn't below `P` is rejected.
+#[cfg(feature = "serde")]
+impl<'de, const P: u64> serde::Deserialize<'de> for Modular<P> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "Modular")]
+        struct Raw(u64);
+
+        let Raw(value) = Raw::deserialize(deserializer)?;
+        if P <= value {
+            return Err(D::Error::custom(format!("{value} is not below the modulus {P}")));
+        }
+        Ok(Self(value))
+    }
+}
+
+impl<const P: u64> Display for Modular<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const P: u64> PuzzleNumber for Modular<P> {
+    #[inline]
+    fn from_integer(value: isize) -> Self {
+        Self::new(value as i128)
+    }
+
+    #[inline]
+    fn to_integer(&self) -> Option<isize> {
+        isize::try_from(self.0).ok()
+    }
+
+    #[inline]
+    fn checked_add(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        Ok(Self::new(self.0 as i128 + rhs.0 as i128))
+    }
+
+    #[inline]
+    fn checked_sub(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        Ok(Self::new(self.0 as i128 - rhs.0 as i128))
+    }
+
+    #[inline]
+    fn checked_mul(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        Ok(self.mul(*rhs))
+    }
+
+    #[inline]
+    fn checked_div(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        Ok(self.mul(rhs.inverse()?))
+    }
+
+    /// There is no remainder modulo `P`, so this is always [`ArithmeticError::Undefined`].
+    #[inline]
+    fn checked_rem(&self, _rhs: &Self) -> Result<Self, ArithmeticError> {
+        Err(ArithmeticError::Undefined)
+    }
+
+    fn checked_pow(&self, exp: isize) -> Result<Self, ArithmeticError> {
+        let base = if exp < 0 { self.inverse()? } else { *self };
+        Ok(base.pow_unsigned(exp.unsigned_abs()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answer::{ANSWER_ATTEMPTS, AnswerInfo, generate_constrained_stage_from_seed};
+    use crate::answer::constraints::{ConstraintError, GeneratorConstraints};
+    use crate::answer::pool::NumberPool;
+    use crate::answer::seeded_rng;
+    use crate::calculator::small_size::SmallSizeCalculator;
+    use crate::move_dir::MoveDir;
+    use crate::number::modular::Modular;
+    use crate::number::PuzzleNumber;
+    use crate::operation::Operation;
+    use crate::ratio::ArithmeticError;
+    use crate::rule_set::RuleSet;
+    use crate::stage::Stage;
+
+    type Mod7 = Modular<7>;
+
+    #[test]
+    fn arithmetic() {
+        let (three, five) = (Mod7::new(3), Mod7::new(5));
+        assert_eq!(three.checked_add(&five), Ok(Mod7::new(1)));
+        assert_eq!(three.checked_sub(&five), Ok(Mod7::new(5)));
+        assert_eq!(three.checked_mul(&five), Ok(Mod7::new(1)));
+        assert_eq!(three.checked_div(&five), Ok(Mod7::new(2)));
+        assert_eq!(three.checked_pow(-1), Ok(five));
+        assert_eq!(three.checked_pow(6), Ok(Mod7::new(1)));
+        assert_eq!(three.checked_div(&Mod7::new(0)), Err(ArithmeticError::DivisionByZero));
+        assert_eq!(Mod7::from_integer(-1), Mod7::new(6));
+    }
+
+    #[test]
+    fn composite_modulus() {
+        assert_eq!(Modular::<12>::new(4).inverse(), Err(ArithmeticError::Undefined));
+        assert_eq!(Modular::<12>::new(5).inverse(), Ok(Modular::new(5)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize() {
+        assert_eq!(serde_json::from_str::<Mod7>("6").unwrap(), Mod7::new(6));
+        assert!(serde_json::from_str::<Mod7>("7").is_err());
+        assert!(serde_json::from_str::<Mod7>("100").is_err());
+        assert_eq!(serde_json::from_str::<Mod7>(&serde_json::to_string(&Mod7::new(-1)).unwrap()).unwrap(), Mod7::new(6));
+    }
+
+    #[test]
+    fn stage() {
+        let mut stage = Stage::<4, SmallSizeCalculator, Mod7>::new(SmallSizeCalculator, [2, 6, 1, 3].map(Mod7::new));
+        // 1 / 2
+        stage.move_cell(0, MoveDir::RightDown).unwrap();
+        assert_eq!(stage.ratios()[2], Some(Mod7::new(4)));
+        // 3 - 4
+        stage.move_cell(2, MoveDir::RightUp).unwrap();
+        assert_eq!(stage.ratios()[3], Some(Mod7::new(6)));
+        // 6 / 6
+        stage.move_cell(1, MoveDir::RightDown).unwrap();
+        assert_eq!(stage.last_ratio(), Some(Mod7::new(1)));
+    }
+
+    #[test]
+    fn generate() {
+        let stage = Stage::<4, SmallSizeCalculator, Mod7>::new(SmallSizeCalculator, [1, 2, 3, 4].map(Mod7::new));
+        let answer = AnswerInfo::generate_on_stage(&stage, &mut seeded_rng(1)).unwrap();
+        let mut replay = stage.clone();
+        for (cell_no, dir) in &answer.steps {
+            replay.move_cell(*cell_no, *dir).unwrap();
+        }
+        assert_eq!(replay.last_ratio(), Some(answer.ratio));
+    }
+
+    #[test]
+    fn generate_constrained() {
+        let pool = NumberPool::weighted((1..7).map(|n| (Mod7::new(n), 1))).with_duplicates(true);
+        let constraints = GeneratorConstraints {
+            min_goal: Some(Mod7::new(5)),
+            min_combines: 3,
+            ..Default::default()
+        };
+        for seed in 0..20 {
+            let (ratios, answer) = generate_constrained_stage_from_seed::<4, SmallSizeCalculator, _>(seed, &pool, &constraints).unwrap();
+            assert!(Mod7::new(5) <= answer.ratio);
+            let stage = Stage::<4, SmallSizeCalculator, Mod7>::new(SmallSizeCalculator, ratios);
+            assert_eq!(answer.steps.verify(&stage, answer.ratio), Ok(answer.ratio));
+            assert_eq!(answer.steps.display(&stage).to_string().lines().count(), answer.steps.len());
+        }
+    }
+
+    #[test]
+    fn no_answer() {
+        // There is no remainder modulo 7, so no cells can ever be combined.
+        let stage = Stage::<4, SmallSizeCalculator, Mod7>::with_rules(SmallSizeCalculator, RuleSet::new(Operation::Rem), [1, 2, 3, 4].map(Mod7::new));
+        assert_eq!(
+            AnswerInfo::generate_on_stage(&stage, &mut seeded_rng(1)),
+            Err(ConstraintError::GaveUp { attempts: ANSWER_ATTEMPTS })
+        );
+    }
+}
@@ -0,0 +1,130 @@
+//! Rationals of integers wider than `isize` as cell values, from the `num` crate.
+
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+
+use num::traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, FromPrimitive, One, Signed, ToPrimitive, Zero};
+use num::Integer;
+
+use crate::number::PuzzleNumber;
+use crate::ratio::ArithmeticError;
+
+/// A rational of `i128`, for longer chains of operations before overflowing.
+pub type Rational128 = num::rational::Ratio<i128>;
+
+/// A rational of arbitrary precision, which never overflows.
+pub type BigRational = num::BigRational;
+
+impl<T> PuzzleNumber for num::rational::Ratio<T>
+    where T: Clone + Integer + Signed + CheckedAdd + CheckedSub + CheckedMul + FromPrimitive + ToPrimitive + Hash + Debug + Display + Send + Sync + 'static
+{
+    /// ## Panics
+    ///
+    /// Panics if `value` doesn't fit in `T`.
+    #[inline]
+    fn from_integer(value: isize) -> Self {
+        Self::from(T::from_isize(value).expect("the integer type is narrower than isize"))
+    }
+
+    #[inline]
+    fn to_integer(&self) -> Option<isize> {
+        if self.is_integer() {
+            self.numer().to_isize()
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn checked_add(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        CheckedAdd::checked_add(self, rhs).ok_or(ArithmeticError::Overflow)
+    }
+
+    #[inline]
+    fn checked_sub(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        CheckedSub::checked_sub(self, rhs).ok_or(ArithmeticError::Overflow)
+    }
+
+    #[inline]
+    fn checked_mul(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        CheckedMul::checked_mul(self, rhs).ok_or(ArithmeticError::Overflow)
+    }
+
+    fn checked_div(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        if rhs.is_zero() {
+            return Err(ArithmeticError::DivisionByZero);
+        }
+        CheckedDiv::checked_div(self, rhs).ok_or(ArithmeticError::Overflow)
+    }
+
+    fn checked_rem(&self, rhs: &Self) -> Result<Self, ArithmeticError> {
+        let floor = PuzzleNumber::checked_div(self, rhs)?.floor();
+        PuzzleNumber::checked_sub(self, &PuzzleNumber::checked_mul(rhs, &floor)?)
+    }
+
+    fn checked_pow(&self, exp: isize) -> Result<Self, ArithmeticError> {
+        let base = if exp < 0 {
+            PuzzleNumber::checked_div(&Self::one(), self)?
+        } else {
+            self.clone()
+        };
+        num::traits::checked_pow(base, exp.unsigned_abs()).ok_or(ArithmeticError::Overflow)
+    }
+
+    #[inline]
+    fn is_fraction(&self) -> bool {
+        !self.is_integer()
+    }
+
+    #[inline]
+    fn is_negative(&self) -> bool {
+        self.numer().is_negative()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigInt;
+
+    use crate::calculator::small_size::SmallSizeCalculator;
+    use crate::move_dir::MoveDir;
+    use crate::number::rational::{BigRational, Rational128};
+    use crate::number::PuzzleNumber;
+    use crate::ratio::ArithmeticError;
+    use crate::stage::outcome::MoveError;
+    use crate::stage::Stage;
+
+    #[test]
+    fn arithmetic() {
+        let lhs = Rational128::new(7, 2);
+        let rhs = Rational128::new(-3, 4);
+        assert_eq!(PuzzleNumber::checked_rem(&lhs, &rhs), Ok(Rational128::new(-1, 4)));
+        assert_eq!(PuzzleNumber::checked_pow(&rhs, -2), Ok(Rational128::new(16, 9)));
+        assert_eq!(PuzzleNumber::checked_div(&lhs, &Rational128::from(0)), Err(ArithmeticError::DivisionByZero));
+        assert_eq!(PuzzleNumber::to_integer(&Rational128::new(6, 2)), Some(3));
+        assert!(PuzzleNumber::is_fraction(&lhs) && PuzzleNumber::is_negative(&rhs));
+    }
+
+    #[test]
+    fn wider_than_ratio() {
+        let big = isize::MAX / 2;
+        // Moving the top cell down left multiplies the left one by it, which overflows a `Ratio`.
+        let mut stage = Stage::<4, SmallSizeCalculator>::from([4, big, 1, 3]);
+        assert_eq!(stage.move_cell(1, MoveDir::LeftDown), Err(MoveError::Overflow));
+
+        let cells = [4, big as i128, 1, 3].map(Rational128::from);
+        let mut stage = Stage::<4, SmallSizeCalculator, Rational128>::new(SmallSizeCalculator, cells);
+        stage.move_cell(1, MoveDir::LeftDown).unwrap();
+        assert_eq!(stage.ratios()[0], Some(Rational128::from(big as i128 * 4)));
+    }
+
+    #[test]
+    fn arbitrary_precision() {
+        let huge = BigRational::from(BigInt::from(2)).pow(100);
+        let cells = [huge.clone(), huge.clone(), <BigRational as PuzzleNumber>::from_integer(1), <BigRational as PuzzleNumber>::from_integer(3)];
+        let mut stage = Stage::<4, SmallSizeCalculator, BigRational>::new(SmallSizeCalculator, cells);
+        stage.move_cell(1, MoveDir::LeftDown).unwrap();
+        assert_eq!(stage.ratios()[0], Some(huge.pow(2)));
+        assert_eq!(PuzzleNumber::to_integer(&huge), None);
+    }
+}
@@ -0,0 +1,150 @@
+
+use crate::number::PuzzleNumber;
+use crate::ratio::ArithmeticError;
+
+/// The operation performed on two cells when a cell is moved.
+///
+/// Which direction performs which operation is decided by [`crate::rule_set::RuleSet`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Reflect))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Operation {
+    /// `md + ms`
+    Add,
+    /// `md - ms`
+    Sub,
+    /// `md * ms`
+    Mul,
+    /// `md / ms`
+    Div,
+    /// Swap `md` and `ms`.
+    Swap,
+    /// `md mod ms`, the remainder of the floored division.
+    Rem,
+    /// `md ^ ms`; `ms` must be an integer.
+    Pow,
+    /// The larger of `md` and `ms`.
+    Max,
+    /// The smaller of `md` and `ms`.
+    Min,
+    /// The digits of `ms` appended to the digits of `md`;
+    /// both must be non-negative integers.
+    Concat,
+}
+
+impl Operation {
+    /// All operations, in declaration order.
+    pub const ALL: [Operation; 10] = [
+        Operation::Add,
+        Operation::Sub,
+        Operation::Mul,
+        Operation::Div,
+        Operation::Swap,
+        Operation::Rem,
+        Operation::Pow,
+        Operation::Max,
+        Operation::Min,
+        Operation::Concat,
+    ];
+
+    #[inline]
+    pub const fn is_swap(&self) -> bool {
+        matches!(self, Operation::Swap)
+    }
+
+    /// Returns a short ASCII symbol of this operation, used to label arrows.
+    pub const fn symbol(&self) -> &'static str {
+        match self {
+            Operation::Add => "+",
+            Operation::Sub => "-",
+            Operation::Mul => "*",
+            Operation::Div => "/",
+            Operation::Swap => "<>",
+            Operation::Rem => "%",
+            Operation::Pow => "^",
+            Operation::Max => "max",
+            Operation::Min => "min",
+            Operation::Concat => "..",
+        }
+    }
+
+    /// Returns how this operation combines `md` and `ms`, as written in the rule table.
+    pub const fn expression(&self) -> &'static str {
+        match self {
+            Operation::Add => "md + ms",
+            Operation::Sub => "md - ms",
+            Operation::Mul => "md * ms",
+            Operation::Div => "md / ms",
+            Operation::Swap => "swap md and ms",
+            Operation::Rem => "md mod ms",
+            Operation::Pow => "md ^ ms",
+            Operation::Max => "max(md, ms)",
+            Operation::Min => "min(md, ms)",
+            Operation::Concat => "digits of md followed by ms",
+        }
+    }
+
+    /// Calculates the new value of the destination cell `md` from the source cell `ms`.
+    ///
+    /// A swap doesn't combine the cells, so it returns `md` as is.
+    pub fn apply<Num: PuzzleNumber>(&self, md: Num, ms: Num) -> Result<Num, ArithmeticError> {
+        match self {
+            Operation::Add => md.checked_add(&ms),
+            Operation::Sub => md.checked_sub(&ms),
+            Operation::Mul => md.checked_mul(&ms),
+            Operation::Div => md.checked_div(&ms),
+            Operation::Swap => Ok(md),
+            Operation::Rem => md.checked_rem(&ms),
+            Operation::Pow => md.checked_pow(ms.to_integer().ok_or(ArithmeticError::Undefined)?),
+            Operation::Max => Ok(md.max(ms)),
+            Operation::Min => Ok(md.min(ms)),
+            Operation::Concat => concat(md, ms),
+        }
+    }
+}
+
+fn concat<Num: PuzzleNumber>(md: Num, ms: Num) -> Result<Num, ArithmeticError> {
+    let (Some(md), Some(ms)) = (md.to_integer(), ms.to_integer()) else {
+        return Err(ArithmeticError::Undefined);
+    };
+    if md < 0 || ms < 0 {
+        return Err(ArithmeticError::Undefined);
+    }
+    let digits = ms.checked_ilog10().unwrap_or(0) + 1;
+    10_isize
+        .checked_pow(digits)
+        .and_then(|shift| md.checked_mul(shift))
+        .and_then(|shifted| shifted.checked_add(ms))
+        .map(Num::from_integer)
+        .ok_or(ArithmeticError::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroIsize;
+
+    use crate::operation::Operation;
+    use crate::ratio::{ArithmeticError, Ratio};
+
+    #[test]
+    fn pow() {
+        assert_eq!(Operation::Pow.apply(Ratio::from(2), Ratio::from(3)), Ok(Ratio::from(8)));
+        let half = Ratio::new(1, NonZeroIsize::new(2).unwrap());
+        assert_eq!(Operation::Pow.apply(Ratio::from(2), half), Err(ArithmeticError::Undefined));
+    }
+
+    #[test]
+    fn max_min() {
+        let half = Ratio::new(1, NonZeroIsize::new(2).unwrap());
+        assert_eq!(Operation::Max.apply(half, Ratio::from(-3)), Ok(half));
+        assert_eq!(Operation::Min.apply(half, Ratio::from(-3)), Ok(Ratio::from(-3)));
+    }
+
+    #[test]
+    fn concat() {
+        assert_eq!(Operation::Concat.apply(Ratio::from(12), Ratio::from(3)), Ok(Ratio::from(123)));
+        assert_eq!(Operation::Concat.apply(Ratio::from(1), Ratio::from(0)), Ok(Ratio::from(10)));
+        assert_eq!(Operation::Concat.apply(Ratio::from(1), Ratio::from(-2)), Err(ArithmeticError::Undefined));
+        assert_eq!(Operation::Concat.apply(Ratio::from(isize::MAX), Ratio::from(1)), Err(ArithmeticError::Overflow));
+    }
+}
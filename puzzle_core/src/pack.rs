@@ -0,0 +1,382 @@
+//! Generates packs of many stages at once for offline play.
+//!
+//! Candidate stages come from consecutive seeds and are generated on several threads,
+//! but they are accepted in seed order, so the same [`PackOptions`] always produce the same [`Pack`].
+
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::num::NonZeroUsize;
+use std::thread;
+
+use crate::answer::constraints::{ConstraintError, GeneratorConstraints};
+use crate::answer::pool::{NumberPool, PoolError};
+use crate::answer::{AnswerInfo, generate_constrained_stage_from_seed};
+use crate::calculator::Calculator;
+use crate::difficulty::DifficultyBand;
+use crate::notation::Notation;
+use crate::ratio::Ratio;
+use crate::stage::RatioArray;
+use crate::topology::Topology;
+
+/// The version written to [`Pack::version`]; bumped whenever the format changes.
+pub const PACK_VERSION: u32 = 1;
+
+/// The number of candidate seeds each thread generates per round.
+const SEEDS_PER_THREAD: usize = 16;
+
+/// A versioned list of stages.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pack {
+    /// [`PACK_VERSION`] at the time the pack was written.
+    pub version: u32,
+    /// The [`Topology::name`] of the board every stage is played on.
+    pub board: String,
+    /// The seed the pack was generated from.
+    pub seed: u64,
+    /// The stages from the easiest; each has its own seed and reference solution.
+    pub stages: Vec<Notation>,
+}
+
+/// How many stages of each difficulty a pack contains.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub struct DifficultyMix {
+    pub easy: usize,
+    pub medium: usize,
+    pub hard: usize,
+    /// Stages of any difficulty, including ones too large to be rated.
+    pub any: usize,
+}
+
+impl DifficultyMix {
+    #[inline]
+    pub const fn total(&self) -> usize {
+        self.easy + self.medium + self.hard + self.any
+    }
+
+    /// Takes a slot for a stage of `band`, falling back to [`DifficultyMix::any`].
+    ///
+    /// Returns `false` if no slot is left.
+    fn take(&mut self, band: Option<DifficultyBand>) -> bool {
+        let slot = match band {
+            Some(DifficultyBand::Easy) => &mut self.easy,
+            Some(DifficultyBand::Medium) => &mut self.medium,
+            Some(DifficultyBand::Hard) => &mut self.hard,
+            None => &mut self.any,
+        };
+        if let Some(left) = slot.checked_sub(1) {
+            *slot = left;
+            true
+        } else if let Some(left) = self.any.checked_sub(1) {
+            self.any = left;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PackOptions {
+    /// The seed of the first candidate; the following seeds are tried in order.
+    pub seed: u64,
+    pub mix: DifficultyMix,
+    /// The values the cells start with; [`NumberPool::for_cells`] if `None`.
+    pub pool: Option<NumberPool>,
+    /// The constraints every stage is generated with.
+    pub constraints: GeneratorConstraints,
+    /// The number of candidate seeds tried before giving up.
+    pub max_seeds: u64,
+    pub threads: NonZeroUsize,
+}
+
+impl PackOptions {
+    /// Creates options for `mix` with the default pool and without constraints, trying up to a thousand seeds per stage
+    /// on as many threads as are available.
+    pub fn new(seed: u64, mix: DifficultyMix) -> Self {
+        Self {
+            seed,
+            mix,
+            pool: None,
+            constraints: GeneratorConstraints::default(),
+            max_seeds: (mix.total() as u64).saturating_mul(1000),
+            threads: thread::available_parallelism().unwrap_or(NonZeroUsize::MIN),
+        }
+    }
+}
+
+/// The error returned when a pack can't be filled.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum PackError {
+    /// [`PackOptions::pool`] can't fill a stage.
+    Pool(PoolError),
+    /// No stage can meet [`PackOptions::constraints`].
+    Constraints(ConstraintError),
+    NotEnough {
+        /// The number of stages found before giving up.
+        found: usize,
+        requested: usize,
+        tried_seeds: u64,
+    },
+}
+
+impl Display for PackError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PackError::Pool(e) => write!(f, "{e}"),
+            PackError::Constraints(e) => write!(f, "{e}"),
+            PackError::NotEnough { found, requested, tried_seeds } => {
+                write!(f, "only {found} of {requested} stages were found within {tried_seeds} seeds")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PackError {}
+
+/// Generates a pack of distinct stages that satisfies `options`.
+///
+/// A seed whose stage can't meet the constraints is skipped.
+/// Stages with the same goal and the same cells, or cells that are mirror images of each other on the board,
+/// count as duplicates, and only the one with the smallest seed is kept.
+pub fn generate_pack<const STAGE_SIZE: usize, Calc>(options: &PackOptions) -> Result<Pack, PackError>
+    where Calc: Calculator + Default + Clone + 'static
+{
+    let calculator = Calc::default();
+    let topology = calculator.topology();
+    let pool = options.pool.clone().unwrap_or_else(|| NumberPool::for_cells(STAGE_SIZE));
+    pool.check(STAGE_SIZE).map_err(PackError::Pool)?;
+    options.constraints.check(STAGE_SIZE).map_err(PackError::Constraints)?;
+    let symmetries = mirror_symmetries(topology);
+    let mut mix = options.mix;
+    let mut seen = HashSet::new();
+    let mut stages = Vec::with_capacity(mix.total());
+    let mut tried = 0;
+
+    while 0 < mix.total() {
+        if options.max_seeds <= tried {
+            return Err(PackError::NotEnough {
+                found: stages.len(),
+                requested: options.mix.total(),
+                tried_seeds: tried,
+            });
+        }
+        let round = (options.threads.get() * SEEDS_PER_THREAD).min((options.max_seeds - tried) as usize);
+        let first = options.seed.wrapping_add(tried);
+        for (seed, (ratios, answer)) in generate_round::<STAGE_SIZE, Calc>(first, round, &pool, options) {
+            if mix.total() == 0 {
+                break;
+            }
+            if !seen.insert(stage_key(&symmetries, &ratios, answer.ratio)) {
+                continue;
+            }
+            let band = answer.difficulty.as_ref().map(|d| d.band());
+            if !mix.take(band) {
+                continue;
+            }
+            let score = answer.difficulty.as_ref().map_or(u32::MAX, |d| d.score);
+            let mut notation = Notation::new(topology, &ratios, answer.ratio, answer.steps);
+            notation.seed = Some(seed);
+            notation.difficulty = band;
+            stages.push((score, notation));
+        }
+        tried += round as u64;
+    }
+
+    // Stable, so stages of the same score stay in seed order.
+    stages.sort_by_key(|(score, _)| *score);
+    Ok(Pack {
+        version: PACK_VERSION,
+        board: topology.name(),
+        seed: options.seed,
+        stages: stages.into_iter().map(|(_, notation)| notation).collect(),
+    })
+}
+
+/// Generates the stages of `count` seeds from `first` and returns them in seed order,
+/// leaving out the seeds whose stage can't meet the constraints.
+fn generate_round<const STAGE_SIZE: usize, Calc>(
+    first: u64,
+    count: usize,
+    pool: &NumberPool,
+    options: &PackOptions,
+) -> Vec<(u64, (RatioArray<STAGE_SIZE>, AnswerInfo))>
+    where Calc: Calculator + Default + Clone + 'static
+{
+    let threads = options.threads.get();
+    let constraints = &options.constraints;
+    let mut results = thread::scope(|scope| {
+        let handles = (0..threads)
+            .map(|t| scope.spawn(move || {
+                (t..count)
+                    .step_by(threads)
+                    .filter_map(|i| {
+                        let seed = first.wrapping_add(i as u64);
+                        let stage = generate_constrained_stage_from_seed::<STAGE_SIZE, Calc, _>(seed, pool, constraints).ok()?;
+                        Some((seed, stage))
+                    })
+                    .collect::<Vec<_>>()
+            }))
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("a generator thread panicked"))
+            .collect::<Vec<_>>()
+    });
+    results.sort_by_key(|(seed, _)| seed.wrapping_sub(first));
+    results
+}
+
+/// Returns the permutations of the cells that mirror the board horizontally, vertically or both,
+/// including the identity.
+///
+/// `permutation[i]` is the cell that cell `i` is mirrored onto.
+/// Mirrors that don't map every cell onto another cell are left out.
+fn mirror_symmetries(topology: &impl Topology) -> Vec<Vec<usize>> {
+    let cells = topology.cell_count();
+    [(1., 1.), (-1., 1.), (1., -1.), (-1., -1.)]
+        .into_iter()
+        .filter_map(|(sx, sy)| {
+            (0..cells)
+                .map(|i| {
+                    let p = topology.position(i);
+                    (0..cells).find(|&j| {
+                        let q = topology.position(j);
+                        (q.x - p.x * sx).abs() < 1e-3 && (q.y - p.y * sy).abs() < 1e-3
+                    })
+                })
+                .collect::<Option<Vec<_>>>()
+        })
+        .collect()
+}
+
+/// Returns the same key for a stage and all its mirror images.
+fn stage_key<const STAGE_SIZE: usize>(
+    symmetries: &[Vec<usize>],
+    ratios: &RatioArray<STAGE_SIZE>,
+    goal: Ratio,
+) -> (Vec<(isize, isize)>, (isize, isize)) {
+    let key = symmetries
+        .iter()
+        .map(|permutation| {
+            let mut cells = vec![(0, 0); STAGE_SIZE];
+            for (i, ratio) in ratios.iter().enumerate() {
+                cells[permutation[i]] = (ratio.numer, ratio.denom);
+            }
+            cells
+        })
+        .min()
+        .unwrap_or_default();
+    (key, (goal.numer, goal.denom))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::num::NonZeroUsize;
+
+    use crate::answer::constraints::ConstraintError;
+    use crate::answer::pool::{NumberPool, PoolError};
+    use crate::calculator::small_size::SmallSizeCalculator;
+    use crate::difficulty::DifficultyBand;
+    #[cfg(feature = "serde")]
+    use crate::pack::Pack;
+    use crate::pack::{DifficultyMix, generate_pack, mirror_symmetries, PACK_VERSION, PackError, PackOptions, stage_key};
+    use crate::ratio::Ratio;
+    use crate::topology::diamond::DiamondTopology;
+
+    #[test]
+    fn diamond_mirrors() {
+        let symmetries = mirror_symmetries(&DiamondTopology);
+        assert_eq!(symmetries, [
+            vec![0, 1, 2, 3],
+            vec![3, 1, 2, 0],
+            vec![0, 2, 1, 3],
+            vec![3, 2, 1, 0],
+        ]);
+        let key = stage_key(&symmetries, &[1, 2, 3, 4].map(Ratio::from), Ratio::from(10));
+        assert_eq!(key, stage_key(&symmetries, &[4, 2, 3, 1].map(Ratio::from), Ratio::from(10)));
+        assert_eq!(key, stage_key(&symmetries, &[4, 3, 2, 1].map(Ratio::from), Ratio::from(10)));
+        assert_ne!(key, stage_key(&symmetries, &[2, 1, 3, 4].map(Ratio::from), Ratio::from(10)));
+    }
+
+    #[test]
+    fn same_pack_on_any_number_of_threads() {
+        let mut options = options();
+        let pack = generate_pack::<4, SmallSizeCalculator>(&options).unwrap();
+        options.threads = NonZeroUsize::new(3).unwrap();
+        assert_eq!(generate_pack::<4, SmallSizeCalculator>(&options).unwrap(), pack);
+        assert_eq!(pack.version, PACK_VERSION);
+        assert_eq!(pack.board, "diamond");
+    }
+
+    #[test]
+    fn mix_and_goal_range() {
+        let pack = generate_pack::<4, SmallSizeCalculator>(&options()).unwrap();
+        let count = |band| pack.stages.iter().filter(|s| s.difficulty == Some(band)).count();
+        // The stage of any difficulty may fall into either band.
+        assert!(3 <= count(DifficultyBand::Easy));
+        assert!(2 <= count(DifficultyBand::Medium));
+        assert_eq!(pack.stages.len(), 6);
+        let constraints = options().constraints;
+        assert!(pack.stages.iter().all(|s| constraints.accepts_goal(s.goal, &s.cells.iter().copied().map(Some).collect::<Vec<_>>())));
+
+        let symmetries = mirror_symmetries(&DiamondTopology);
+        let keys = pack.stages
+            .iter()
+            .map(|s| stage_key(&symmetries, &<[Ratio; 4]>::try_from(s.cells.as_slice()).unwrap(), s.goal))
+            .collect::<HashSet<_>>();
+        assert_eq!(keys.len(), pack.stages.len());
+    }
+
+    #[test]
+    fn give_up() {
+        let mut options = options();
+        options.constraints.min_goal = Some(Ratio::from(1_000_000));
+        options.constraints.max_goal = None;
+        options.constraints.max_attempts = 4;
+        options.max_seeds = 100;
+        assert_eq!(generate_pack::<4, SmallSizeCalculator>(&options), Err(PackError::NotEnough {
+            found: 0,
+            requested: 6,
+            tried_seeds: 100,
+        }));
+        options.constraints.max_goal = Some(Ratio::from(0));
+        assert_eq!(
+            generate_pack::<4, SmallSizeCalculator>(&options),
+            Err(PackError::Constraints(ConstraintError::Contradictory))
+        );
+        options.pool = Some(NumberPool::range(1, 3).unwrap());
+        assert_eq!(
+            generate_pack::<4, SmallSizeCalculator>(&options),
+            Err(PackError::Pool(PoolError::TooFewValues { values: 3, cells: 4 }))
+        );
+    }
+
+    /// The pack the game ships with, loaded the way its `PackLoader` does.
+    #[cfg(feature = "serde")]
+    #[test]
+    fn campaign_pack() {
+        let pack: Pack = serde_json::from_str(include_str!("../../bevy/assets/packs/campaign.pack.json")).unwrap();
+        assert_eq!(pack.version, PACK_VERSION);
+        assert!(!pack.stages.is_empty());
+        for notation in &pack.stages {
+            let stage = notation.to_stage::<4, _>(SmallSizeCalculator).unwrap();
+            assert_eq!(notation.steps.verify(&stage, notation.goal), Ok(notation.goal));
+        }
+    }
+
+    fn options() -> PackOptions {
+        let mut options = PackOptions::new(1, DifficultyMix {
+            easy: 3,
+            medium: 2,
+            hard: 0,
+            any: 1,
+        });
+        options.constraints.min_goal = Some(Ratio::from(0));
+        options.constraints.max_goal = Some(Ratio::from(20));
+        options.constraints.integer_goal = true;
+        options.threads = NonZeroUsize::MIN;
+        options
+    }
+}
@@ -1,11 +1,20 @@
+use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
 use std::num::NonZeroIsize;
-use std::ops::{Add, Div, Mul, Sub};
+use std::iter::{Product, Sum};
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+pub mod format;
+pub mod num_traits;
+pub mod parse;
 
 /// Ratio represents a divisor.
 /// This struct must be created by [`Ratio::new`] to ensure that [`Ratio::denom`] is non-zero.
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+///
+/// A ratio is always reduced and its denominator is positive, so equal values are equal ratios.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "bevy", derive(bevy::prelude::Reflect))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[non_exhaustive]
 pub struct Ratio {
     /// numerator
@@ -14,13 +23,240 @@ pub struct Ratio {
     pub denom: isize,
 }
 
+/// The error returned by the checked arithmetic of [`Ratio`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ArithmeticError {
+    /// The numerator or the denominator of the result doesn't fit in `isize`.
+    Overflow,
+    /// The divisor is zero.
+    DivisionByZero,
+    /// The operation isn't defined for the operands, such as a fractional exponent.
+    Undefined,
+}
+
+impl Display for ArithmeticError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArithmeticError::Overflow => f.write_str("arithmetic overflow"),
+            ArithmeticError::DivisionByZero => f.write_str("division by zero"),
+            ArithmeticError::Undefined => f.write_str("undefined operation"),
+        }
+    }
+}
+
+impl std::error::Error for ArithmeticError {}
+
 impl Ratio {
+    pub const ZERO: Self = Self { numer: 0, denom: 1 };
+
+    pub const ONE: Self = Self { numer: 1, denom: 1 };
+
+    /// Creates a reduced ratio, moving the sign to the numerator.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the result can't be represented, such as `isize::MIN / -1`;
+    /// use [`Ratio::checked_new`] to handle it.
+    #[inline]
     pub fn new(numer: isize, denom: NonZeroIsize) -> Self {
-        let gcd = num::integer::gcd(numer, denom.get());
-        Self {
-            numer: numer / gcd,
-            denom: denom.get() / gcd,
+        Self::checked_new(numer, denom).expect("attempt to negate with overflow")
+    }
+
+    /// Creates a reduced ratio, moving the sign to the numerator,
+    /// or returns [`ArithmeticError::Overflow`] if the result can't be represented.
+    pub fn checked_new(numer: isize, denom: NonZeroIsize) -> Result<Self, ArithmeticError> {
+        // Reduced without a sign, as the gcd of two `isize::MIN` doesn't fit in `isize`.
+        let gcd = num::integer::gcd(numer.unsigned_abs(), denom.get().unsigned_abs());
+        let abs_numer = numer.unsigned_abs() / gcd;
+        let abs_denom = denom.get().unsigned_abs() / gcd;
+        let numer = if (numer < 0) != (denom.get() < 0) {
+            0isize.checked_sub_unsigned(abs_numer)
+        } else {
+            isize::try_from(abs_numer).ok()
+        };
+        Ok(Self {
+            numer: numer.ok_or(ArithmeticError::Overflow)?,
+            denom: isize::try_from(abs_denom).map_err(|_| ArithmeticError::Overflow)?,
+        })
+    }
+
+    /// Calculates `self + rhs`, or returns [`ArithmeticError::Overflow`]
+    /// if the result can't be represented.
+    pub fn checked_add(self, rhs: Self) -> Result<Self, ArithmeticError> {
+        self.combine_numers(rhs, isize::checked_add)
+    }
+
+    /// Calculates `self - rhs`, or returns [`ArithmeticError::Overflow`]
+    /// if the result can't be represented.
+    ///
+    /// The numerators are subtracted directly, so `isize::MIN - isize::MIN` doesn't overflow.
+    pub fn checked_sub(self, rhs: Self) -> Result<Self, ArithmeticError> {
+        self.combine_numers(rhs, isize::checked_sub)
+    }
+
+    /// Brings both sides to a common denominator and combines the numerators with `op`.
+    fn combine_numers(self, rhs: Self, op: fn(isize, isize) -> Option<isize>) -> Result<Self, ArithmeticError> {
+        let gcd = num::integer::gcd(self.denom, rhs.denom);
+        let l_factor = rhs.denom / gcd;
+        let r_factor = self.denom / gcd;
+        let lcm = self.denom.checked_mul(l_factor).ok_or(ArithmeticError::Overflow)?;
+        let numer = self.numer
+            .checked_mul(l_factor)
+            .zip(rhs.numer.checked_mul(r_factor))
+            .and_then(|(l, r)| op(l, r))
+            .ok_or(ArithmeticError::Overflow)?;
+        Self::checked_new(numer, unsafe {
+            // SAFETY: Safe because lcm is never zero.
+            NonZeroIsize::new_unchecked(lcm)
+        })
+    }
+
+    /// Calculates `-self`, or returns [`ArithmeticError::Overflow`] if `self` is `isize::MIN`.
+    pub fn checked_neg(self) -> Result<Self, ArithmeticError> {
+        let numer = self.numer.checked_neg().ok_or(ArithmeticError::Overflow)?;
+        Ok(Self { numer, denom: self.denom })
+    }
+
+    /// Calculates `self * rhs`, or returns [`ArithmeticError::Overflow`]
+    /// if the result can't be represented.
+    ///
+    /// Both sides are reduced crosswise first,
+    /// so this only fails if the reduced result itself doesn't fit.
+    pub fn checked_mul(self, rhs: Self) -> Result<Self, ArithmeticError> {
+        // Calculated in `i128`, as the gcd of two `isize::MIN` doesn't fit in `isize`
+        // and the product of two `isize` always fits.
+        let l_gcd = num::integer::gcd(self.numer as i128, rhs.denom as i128);
+        let r_gcd = num::integer::gcd(rhs.numer as i128, self.denom as i128);
+        let numer = (self.numer as i128 / l_gcd) * (rhs.numer as i128 / r_gcd);
+        let denom = (self.denom as i128 / r_gcd) * (rhs.denom as i128 / l_gcd);
+        let numer = isize::try_from(numer).map_err(|_| ArithmeticError::Overflow)?;
+        let denom = isize::try_from(denom).map_err(|_| ArithmeticError::Overflow)?;
+        Self::checked_new(numer, unsafe {
+            // SAFETY: Safe because neither denominator is zero.
+            NonZeroIsize::new_unchecked(denom)
+        })
+    }
+
+    /// Calculates `self / rhs`.
+    ///
+    /// Returns [`ArithmeticError::DivisionByZero`] if `rhs` is zero,
+    /// or [`ArithmeticError::Overflow`] if the result can't be represented.
+    pub fn checked_div(self, rhs: Self) -> Result<Self, ArithmeticError> {
+        if rhs.numer == 0 {
+            return Err(ArithmeticError::DivisionByZero);
         }
+        self.checked_mul(Self { numer: rhs.denom, denom: rhs.numer })
+    }
+
+    /// Calculates the remainder of the floored division `self - rhs * floor(self / rhs)`,
+    /// so the result has the sign of `rhs`.
+    pub fn checked_rem(self, rhs: Self) -> Result<Self, ArithmeticError> {
+        let quot = self.checked_div(rhs)?;
+        let floor = num::integer::div_floor(quot.numer, quot.denom);
+        self.checked_sub(rhs.checked_mul(Ratio::from(floor))?)
+    }
+
+    /// Raises `self` to the power of `exp`; a negative `exp` takes the reciprocal.
+    pub fn checked_pow(self, exp: isize) -> Result<Self, ArithmeticError> {
+        let base = if exp < 0 {
+            Ratio::from(1).checked_div(self)?
+        } else {
+            self
+        };
+        // Powers of these stay small however large `exp` is.
+        match (base.numer, base.denom) {
+            (0, 1) if exp != 0 => return Ok(Self::ZERO),
+            (1, 1) => return Ok(Self::ONE),
+            (-1, 1) => return Ok(if exp % 2 == 0 { Self::ONE } else { base }),
+            _ => {}
+        }
+        let exp = u32::try_from(exp.unsigned_abs()).map_err(|_| ArithmeticError::Overflow)?;
+        let numer = base.numer.checked_pow(exp).ok_or(ArithmeticError::Overflow)?;
+        let denom = base.denom.checked_pow(exp).ok_or(ArithmeticError::Overflow)?;
+        Self::checked_new(numer, unsafe {
+            // SAFETY: Safe because a power of a non-zero denominator is never zero.
+            NonZeroIsize::new_unchecked(denom)
+        })
+    }
+
+    /// Raises `self` to the power of `exp`; a negative `exp` takes the reciprocal.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `self` is zero and `exp` is negative, or on overflow;
+    /// use [`Ratio::checked_pow`] to handle them.
+    pub fn pow(self, exp: isize) -> Self {
+        match self.checked_pow(exp) {
+            Ok(ratio) => ratio,
+            Err(ArithmeticError::DivisionByZero) => panic!("attempt to divide by zero"),
+            Err(_) => panic!("attempt to raise to a power with overflow"),
+        }
+    }
+
+    /// Returns the value as an integer if it has no fractional part and fits in `isize`.
+    pub const fn to_integer(&self) -> Option<isize> {
+        match self.numer.checked_rem(self.denom) {
+            Some(0) => self.numer.checked_div(self.denom),
+            _ => None
+        }
+    }
+
+    /// Returns the nearest `f64` to the value.
+    #[inline]
+    pub fn to_f64(&self) -> f64 {
+        self.numer as f64 / self.denom as f64
+    }
+}
+
+impl Default for Ratio {
+    /// Returns zero.
+    #[inline]
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl Ord for Ratio {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Both denominators are positive, so cross-multiplying keeps the order.
+        let lhs = self.numer as i128 * other.denom as i128;
+        let rhs = other.numer as i128 * self.denom as i128;
+        lhs.cmp(&rhs)
+    }
+}
+
+impl PartialOrd for Ratio {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Deserializes through [`Ratio::checked_new`], so a zero denominator or an unreduced value is rejected.
+///
+/// A negative denominator is still accepted and moved to the numerator,
+/// as packs written before the sign was normalized have them.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Ratio {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "Ratio")]
+        struct Raw {
+            numer: isize,
+            denom: isize,
+        }
+
+        let Raw { numer, denom } = Raw::deserialize(deserializer)?;
+        let Some(non_zero) = NonZeroIsize::new(denom) else {
+            return Err(D::Error::custom("the denominator must not be zero"));
+        };
+        let ratio = Self::checked_new(numer, non_zero).map_err(D::Error::custom)?;
+        if ratio.denom.unsigned_abs() != denom.unsigned_abs() {
+            return Err(D::Error::custom(format!("{numer}/{denom} is not reduced")));
+        }
+        Ok(ratio)
     }
 }
 
@@ -35,26 +271,21 @@ impl Display for Ratio {
 }
 
 impl From<isize> for Ratio {
+    #[inline]
     fn from(value: isize) -> Self {
-        Self::new(value, NonZeroIsize::new(1).unwrap())
+        Self { numer: value, denom: 1 }
     }
 }
 
 impl Add for Ratio {
     type Output = Ratio;
 
+    /// ## Panics
+    ///
+    /// Panics on overflow; use [`Ratio::checked_add`] to handle it.
+    #[inline]
     fn add(self, rhs: Self) -> Self::Output {
-        let l_denom = self.denom;
-        let r_denom = rhs.denom;
-
-        let lcm = num::integer::lcm(l_denom, r_denom);
-        Self::new(
-            self.numer * (lcm / l_denom) + rhs.numer * (lcm / r_denom),
-            unsafe {
-                // SAFETY: Safe because lcm is never zero.
-                NonZeroIsize::new_unchecked(lcm)
-            },
-        )
+        self.checked_add(rhs).expect("attempt to add with overflow")
     }
 }
 
@@ -62,8 +293,12 @@ impl Add for Ratio {
 impl Sub for Ratio {
     type Output = Ratio;
 
+    /// ## Panics
+    ///
+    /// Panics on overflow; use [`Ratio::checked_sub`] to handle it.
+    #[inline]
     fn sub(self, rhs: Self) -> Self::Output {
-        self + (rhs * -1)
+        self.checked_sub(rhs).expect("attempt to subtract with overflow")
     }
 }
 
@@ -71,42 +306,122 @@ impl Sub for Ratio {
 impl Mul<isize> for Ratio {
     type Output = Ratio;
 
+    /// ## Panics
+    ///
+    /// Panics on overflow; use [`Ratio::checked_mul`] to handle it.
     #[inline]
     fn mul(self, rhs: isize) -> Self::Output {
-        Self::new(self.numer * rhs, unsafe {
-            // SAFETY: Safe because lcm is never zero.
-            NonZeroIsize::new_unchecked(self.denom)
-        })
+        self * Ratio::from(rhs)
     }
 }
 
 impl Mul for Ratio {
     type Output = Ratio;
 
+    /// ## Panics
+    ///
+    /// Panics on overflow; use [`Ratio::checked_mul`] to handle it.
     #[inline]
     fn mul(self, rhs: Ratio) -> Self::Output {
-        Self::new(self.numer * rhs.numer, unsafe {
-            // SAFETY: Safe because lcm is never zero.
-            NonZeroIsize::new_unchecked(self.denom * rhs.denom)
-        })
+        self.checked_mul(rhs).expect("attempt to multiply with overflow")
     }
 }
 
 
+impl Div<isize> for Ratio {
+    type Output = Ratio;
+
+    /// ## Panics
+    ///
+    /// Panics if `rhs` is zero or on overflow; use [`Ratio::checked_div`] to handle them.
+    #[inline]
+    fn div(self, rhs: isize) -> Self::Output {
+        self / Ratio::from(rhs)
+    }
+}
+
 impl Div<Self> for Ratio {
-    type Output = Option<Self>;
+    type Output = Ratio;
 
+    /// ## Panics
+    ///
+    /// Panics if `rhs` is zero or on overflow; use [`Ratio::checked_div`] to handle them.
     fn div(self, rhs: Self) -> Self::Output {
-        Some(Self::new(self.numer * rhs.denom, NonZeroIsize::new(self.denom * rhs.numer)?))
+        match self.checked_div(rhs) {
+            Ok(ratio) => ratio,
+            Err(ArithmeticError::DivisionByZero) => panic!("attempt to divide by zero"),
+            Err(_) => panic!("attempt to divide with overflow"),
+        }
+    }
+}
+
+
+impl Rem for Ratio {
+    type Output = Ratio;
+
+    /// Returns the remainder of the floored division, like [`Ratio::checked_rem`].
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `rhs` is zero or on overflow; use [`Ratio::checked_rem`] to handle them.
+    fn rem(self, rhs: Self) -> Self::Output {
+        match self.checked_rem(rhs) {
+            Ok(ratio) => ratio,
+            Err(ArithmeticError::DivisionByZero) => panic!("attempt to calculate the remainder with a divisor of zero"),
+            Err(_) => panic!("attempt to calculate the remainder with overflow"),
+        }
+    }
+}
+
+
+impl Neg for Ratio {
+    type Output = Ratio;
+
+    /// ## Panics
+    ///
+    /// Panics if the numerator is `isize::MIN`; use [`Ratio::checked_neg`] to handle it.
+    #[inline]
+    fn neg(self) -> Self::Output {
+        self.checked_neg().expect("attempt to negate with overflow")
+    }
+}
+
+
+impl Sum for Ratio {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Ratio::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Ratio> for Ratio {
+    fn sum<I: Iterator<Item = &'a Ratio>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl Product for Ratio {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Ratio::ONE, Mul::mul)
+    }
+}
+
+impl<'a> Product<&'a Ratio> for Ratio {
+    fn product<I: Iterator<Item = &'a Ratio>>(iter: I) -> Self {
+        iter.copied().product()
     }
 }
 
 
 #[cfg(test)]
 mod tests {
+    use std::cmp::Ordering;
     use std::num::NonZeroIsize;
 
-    use crate::ratio::Ratio;
+    use num::traits::Signed;
+    use proptest::prelude::*;
+
+    use crate::ratio::format::RatioFormat;
+    use crate::ratio::{ArithmeticError, Ratio};
 
     #[test]
     fn approx() {
@@ -164,12 +479,205 @@ mod tests {
     fn div() {
         let lhs = Ratio::new(3, NonZeroIsize::new(4).unwrap());
         let rhs = Ratio::new(11, NonZeroIsize::new(5).unwrap());
-        assert_eq!(lhs / rhs, Some(Ratio { numer: 15, denom: 44 }));
+        assert_eq!(lhs / rhs, Ratio { numer: 15, denom: 44 });
         let lhs = Ratio::new(2, NonZeroIsize::new(3).unwrap());
         let rhs = Ratio::new(2, NonZeroIsize::new(5).unwrap());
-        assert_eq!(lhs / rhs, Some(Ratio { numer: 5, denom: 3 }));
-        let lhs = Ratio::new(2, NonZeroIsize::new(3).unwrap());
+        assert_eq!(lhs / rhs, Ratio { numer: 5, denom: 3 });
+        assert_eq!(lhs / 2, Ratio { numer: 1, denom: 3 });
         let rhs = Ratio::new(0, NonZeroIsize::new(5).unwrap());
-        assert_eq!(lhs / rhs, None);
+        assert_eq!(lhs.checked_div(rhs), Err(ArithmeticError::DivisionByZero));
+    }
+
+    #[test]
+    fn checked() {
+        let lhs = Ratio::new(3, NonZeroIsize::new(4).unwrap());
+        let rhs = Ratio::new(1, NonZeroIsize::new(6).unwrap());
+        assert_eq!(lhs.checked_add(rhs), Ok(lhs + rhs));
+        assert_eq!(lhs.checked_sub(rhs), Ok(lhs - rhs));
+        assert_eq!(lhs.checked_mul(rhs), Ok(lhs * rhs));
+        assert_eq!(lhs.checked_div(rhs), Ok(lhs / rhs));
+        assert_eq!(lhs.checked_rem(rhs), Ok(lhs % rhs));
+        assert_eq!(lhs.checked_neg(), Ok(-lhs));
+    }
+
+    #[test]
+    fn checked_overflow() {
+        let max = Ratio::from(isize::MAX);
+        assert_eq!(max.checked_add(Ratio::from(1)), Err(ArithmeticError::Overflow));
+        assert_eq!(Ratio::from(isize::MIN).checked_sub(Ratio::from(1)), Err(ArithmeticError::Overflow));
+        assert_eq!(max.checked_mul(Ratio::from(2)), Err(ArithmeticError::Overflow));
+        assert_eq!(max.checked_div(Ratio::new(1, NonZeroIsize::new(2).unwrap())), Err(ArithmeticError::Overflow));
+        let tiny = Ratio::new(1, NonZeroIsize::new(isize::MAX).unwrap());
+        assert_eq!(tiny.checked_add(Ratio::new(1, NonZeroIsize::new(2).unwrap())), Err(ArithmeticError::Overflow));
+    }
+
+    #[test]
+    fn checked_min() {
+        let min = Ratio::from(isize::MIN);
+        assert_eq!(min.checked_div(min), Ok(Ratio::from(1)));
+        assert_eq!(min.checked_rem(min), Ok(Ratio::from(0)));
+        assert_eq!(min.checked_mul(min), Err(ArithmeticError::Overflow));
+        assert_eq!(min.checked_mul(Ratio::from(-1)), Err(ArithmeticError::Overflow));
+    }
+
+    #[test]
+    fn checked_mul_reduces_first() {
+        let lhs = Ratio::new(isize::MAX, NonZeroIsize::new(2).unwrap());
+        let rhs = Ratio::new(2, NonZeroIsize::new(isize::MAX).unwrap());
+        assert_eq!(lhs.checked_mul(rhs), Ok(Ratio::from(1)));
+    }
+
+    #[test]
+    fn rem() {
+        assert_eq!(Ratio::from(7).checked_rem(Ratio::from(3)), Ok(Ratio::from(1)));
+        assert_eq!(Ratio::from(-7).checked_rem(Ratio::from(3)), Ok(Ratio::from(2)));
+        // 7/2 - 3/4 * 4 = 1/2
+        let lhs = Ratio::new(7, NonZeroIsize::new(2).unwrap());
+        let rhs = Ratio::new(3, NonZeroIsize::new(4).unwrap());
+        assert_eq!(lhs.checked_rem(rhs), Ok(Ratio::new(1, NonZeroIsize::new(2).unwrap())));
+        assert_eq!(lhs.checked_rem(Ratio::from(0)), Err(ArithmeticError::DivisionByZero));
+    }
+
+    #[test]
+    fn pow() {
+        let ratio = Ratio::new(2, NonZeroIsize::new(3).unwrap());
+        assert_eq!(ratio.checked_pow(2), Ok(Ratio::new(4, NonZeroIsize::new(9).unwrap())));
+        assert_eq!(ratio.checked_pow(-1), Ok(Ratio::new(3, NonZeroIsize::new(2).unwrap())));
+        assert_eq!(ratio.checked_pow(0), Ok(Ratio::from(1)));
+        assert_eq!(Ratio::from(0).checked_pow(-1), Err(ArithmeticError::DivisionByZero));
+        assert_eq!(Ratio::from(10).checked_pow(100), Err(ArithmeticError::Overflow));
+        assert_eq!(Ratio::from(-1).checked_pow(isize::MIN), Ok(Ratio::ONE));
+        assert_eq!(Ratio::from(-1).checked_pow(isize::MAX), Ok(Ratio::from(-1)));
+        assert_eq!(Ratio::from(0).checked_pow(isize::MAX), Ok(Ratio::ZERO));
+        assert_eq!(Ratio::from(2).checked_pow(1 << 32), Err(ArithmeticError::Overflow));
+        assert_eq!(ratio.pow(-2), Ratio::new(9, NonZeroIsize::new(4).unwrap()));
+    }
+
+    #[test]
+    fn normalized_sign() {
+        assert_eq!(Ratio::new(1, NonZeroIsize::new(-2).unwrap()), Ratio { numer: -1, denom: 2 });
+        assert_eq!(Ratio::new(-2, NonZeroIsize::new(-4).unwrap()), Ratio { numer: 1, denom: 2 });
+        assert_eq!(Ratio::checked_new(isize::MIN, NonZeroIsize::new(-1).unwrap()), Err(ArithmeticError::Overflow));
+        assert_eq!(Ratio::checked_new(isize::MIN, NonZeroIsize::new(isize::MIN).unwrap()), Ok(Ratio::from(1)));
+        assert_eq!(Ratio::checked_new(0, NonZeroIsize::new(isize::MIN).unwrap()), Ok(Ratio::from(0)));
+        assert_eq!(Ratio::default(), Ratio::from(0));
+    }
+
+    #[test]
+    fn ord() {
+        let half = Ratio::new(1, NonZeroIsize::new(2).unwrap());
+        let third = Ratio::new(1, NonZeroIsize::new(3).unwrap());
+        assert_eq!(half.cmp(&third), Ordering::Greater);
+        assert!(-half < third);
+        assert_eq!(Ratio::from(isize::MAX).cmp(&Ratio::from(isize::MIN)), Ordering::Greater);
+    }
+
+    #[test]
+    fn sum_and_product() {
+        let ratios = [1, 2, 3, 4].map(|n| Ratio::new(1, NonZeroIsize::new(n).unwrap()));
+        assert_eq!(ratios.iter().sum::<Ratio>(), Ratio::new(25, NonZeroIsize::new(12).unwrap()));
+        assert_eq!(ratios.into_iter().product::<Ratio>(), Ratio::new(1, NonZeroIsize::new(24).unwrap()));
+        assert_eq!(std::iter::empty::<Ratio>().sum::<Ratio>(), Ratio::ZERO);
+        assert_eq!(std::iter::empty::<Ratio>().product::<Ratio>(), Ratio::ONE);
+    }
+
+    #[test]
+    fn to_f64() {
+        assert_eq!(Ratio::new(-3, NonZeroIsize::new(4).unwrap()).to_f64(), -0.75);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize() {
+        let ratio = Ratio::new(3, NonZeroIsize::new(4).unwrap());
+        let json = serde_json::to_string(&ratio).unwrap();
+        assert_eq!(json, r#"{"numer":3,"denom":4}"#);
+        assert_eq!(serde_json::from_str::<Ratio>(&json).unwrap(), ratio);
+        assert!(serde_json::from_str::<Ratio>(r#"{"numer":3,"denom":0}"#).is_err());
+        assert!(serde_json::from_str::<Ratio>(r#"{"numer":2,"denom":4}"#).is_err());
+        assert!(serde_json::from_str::<Ratio>(r#"{"numer":-2,"denom":-4}"#).is_err());
+        assert_eq!(serde_json::from_str::<Ratio>(r#"{"numer":1,"denom":-1}"#).unwrap(), Ratio::from(-1));
+        assert_eq!(serde_json::from_str::<Ratio>(r#"{"numer":-3,"denom":-4}"#).unwrap(), ratio);
+        assert_eq!(ron::from_str::<Ratio>("(numer: -1, denom: 2)").unwrap(), Ratio { numer: -1, denom: 2 });
+    }
+
+    #[test]
+    fn checked_div_by_zero() {
+        assert_eq!(Ratio::from(1).checked_div(Ratio::from(0)), Err(ArithmeticError::DivisionByZero));
+    }
+
+    /// Ratios small enough that sums and products of three of them don't overflow.
+    fn small_ratio() -> impl Strategy<Value = Ratio> {
+        (-1000isize..=1000, (-1000isize..=1000).prop_filter("non-zero", |d| *d != 0))
+            .prop_map(|(numer, denom)| Ratio::new(numer, NonZeroIsize::new(denom).unwrap()))
+    }
+
+    proptest! {
+        #[test]
+        fn prop_normalized(numer in -1000isize..=1000, denom in -1000isize..=1000, k in 1isize..=1000) {
+            prop_assume!(denom != 0);
+            let ratio = Ratio::new(numer, NonZeroIsize::new(denom).unwrap());
+            prop_assert!(0 < ratio.denom);
+            prop_assert_eq!(num::integer::gcd(ratio.numer, ratio.denom), 1);
+            prop_assert_eq!(ratio.numer * denom, numer * ratio.denom);
+            prop_assert_eq!(Ratio::new(numer * -k, NonZeroIsize::new(denom * -k).unwrap()), ratio);
+        }
+
+        #[test]
+        fn prop_field(a in small_ratio(), b in small_ratio(), c in small_ratio()) {
+            prop_assert_eq!(a + b, b + a);
+            prop_assert_eq!(a * b, b * a);
+            prop_assert_eq!((a + b) + c, a + (b + c));
+            prop_assert_eq!((a * b) * c, a * (b * c));
+            prop_assert_eq!(a * (b + c), a * b + a * c);
+            prop_assert_eq!(a - b, a + -b);
+            prop_assert_eq!(a - a, Ratio::ZERO);
+            if b != Ratio::ZERO {
+                prop_assert_eq!(a / b * b, a);
+            }
+        }
+
+        #[test]
+        fn prop_ord(a in small_ratio(), b in small_ratio()) {
+            prop_assert_eq!(a.cmp(&b), a.to_f64().partial_cmp(&b.to_f64()).unwrap());
+            prop_assert_eq!(a == b, a.cmp(&b).is_eq());
+            prop_assert_eq!(a < b, -b < -a);
+        }
+
+        #[test]
+        fn prop_rem(a in small_ratio(), b in small_ratio()) {
+            prop_assume!(b != Ratio::ZERO);
+            let rem = a % b;
+            prop_assert!(rem.abs() < b.abs());
+            prop_assert!(rem == Ratio::ZERO || rem.is_negative() == b.is_negative());
+            prop_assert!(((a - rem) / b).to_integer().is_some());
+        }
+
+        #[test]
+        fn prop_pow(a in small_ratio(), exp in -3isize..=3) {
+            prop_assume!(a != Ratio::ZERO);
+            let expected = (0..exp.unsigned_abs()).map(|_| a).product::<Ratio>();
+            let expected = if exp < 0 { Ratio::ONE / expected } else { expected };
+            prop_assert_eq!(a.pow(exp), expected);
+        }
+
+        #[test]
+        fn prop_pow_unit(exp in any::<isize>()) {
+            let expected_zero = match exp {
+                ..=-1 => Err(ArithmeticError::DivisionByZero),
+                0 => Ok(Ratio::ONE),
+                1.. => Ok(Ratio::ZERO),
+            };
+            prop_assert_eq!(Ratio::ZERO.checked_pow(exp), expected_zero);
+            prop_assert_eq!(Ratio::ONE.checked_pow(exp), Ok(Ratio::ONE));
+            let expected_minus_one = if exp % 2 == 0 { Ratio::ONE } else { -Ratio::ONE };
+            prop_assert_eq!(Ratio::from(-1).checked_pow(exp), Ok(expected_minus_one));
+        }
+
+        #[test]
+        fn prop_parse(a in small_ratio()) {
+            prop_assert_eq!(a.to_string().parse(), Ok(a));
+            prop_assert_eq!(a.display(RatioFormat::Mixed).to_string().parse(), Ok(a));
+        }
     }
 }
\ No newline at end of file
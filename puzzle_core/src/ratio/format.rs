@@ -0,0 +1,228 @@
+//! Writes a [`Ratio`] as an improper fraction, a mixed number, a decimal or with Unicode vulgar fractions.
+
+use std::fmt::{Display, Formatter, Write};
+
+use crate::ratio::Ratio;
+
+/// The number of fractional digits [`RatioFormat::Decimal`] writes before giving up on finding the repeating ones.
+const MAX_DECIMAL_DIGITS: usize = 64;
+
+const VULGAR_FRACTIONS: [(u128, u128, char); 18] = [
+    (1, 2, '½'), (1, 3, '⅓'), (2, 3, '⅔'), (1, 4, '¼'), (3, 4, '¾'),
+    (1, 5, '⅕'), (2, 5, '⅖'), (3, 5, '⅗'), (4, 5, '⅘'), (1, 6, '⅙'), (5, 6, '⅚'),
+    (1, 7, '⅐'), (1, 8, '⅛'), (3, 8, '⅜'), (5, 8, '⅝'), (7, 8, '⅞'), (1, 9, '⅑'), (1, 10, '⅒'),
+];
+
+const SUPERSCRIPTS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+
+const SUBSCRIPTS: [char; 10] = ['₀', '₁', '₂', '₃', '₄', '₅', '₆', '₇', '₈', '₉'];
+
+/// How [`Ratio::display`] writes a value.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Reflect))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RatioFormat {
+    /// `7/2`, the same as the [`Display`] of [`Ratio`].
+    #[default]
+    Improper,
+    /// `3 1/2`.
+    Mixed,
+    /// `3.5`, with the repeating digits in parentheses, such as `0.(3)` for 1/3.
+    Decimal,
+    /// `3½`, or superscript and subscript digits such as `¹¹⁄₁₂` if there is no vulgar fraction for the value.
+    Vulgar,
+}
+
+impl RatioFormat {
+    pub const ALL: [RatioFormat; 4] = [
+        RatioFormat::Improper,
+        RatioFormat::Mixed,
+        RatioFormat::Decimal,
+        RatioFormat::Vulgar,
+    ];
+
+    /// Returns the format after this one in [`RatioFormat::ALL`], going back to the first after the last.
+    pub const fn next(self) -> Self {
+        match self {
+            RatioFormat::Improper => RatioFormat::Mixed,
+            RatioFormat::Mixed => RatioFormat::Decimal,
+            RatioFormat::Decimal => RatioFormat::Vulgar,
+            RatioFormat::Vulgar => RatioFormat::Improper,
+        }
+    }
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            RatioFormat::Improper => "fraction",
+            RatioFormat::Mixed => "mixed number",
+            RatioFormat::Decimal => "decimal",
+            RatioFormat::Vulgar => "vulgar fraction",
+        }
+    }
+}
+
+impl Ratio {
+    /// Returns an object that writes this value in `format`.
+    #[inline]
+    pub const fn display(self, format: RatioFormat) -> RatioDisplay {
+        RatioDisplay {
+            ratio: self,
+            format,
+        }
+    }
+}
+
+/// Writes a [`Ratio`] in a [`RatioFormat`]; see [`Ratio::display`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct RatioDisplay {
+    ratio: Ratio,
+    format: RatioFormat,
+}
+
+impl Display for RatioDisplay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.format == RatioFormat::Improper {
+            return write!(f, "{}", self.ratio);
+        }
+        let Parts { negative, whole, numer, denom } = Parts::of(self.ratio);
+        if negative {
+            f.write_char('-')?;
+        }
+        if numer == 0 {
+            return write!(f, "{whole}");
+        }
+        match self.format {
+            RatioFormat::Improper => unreachable!(),
+            RatioFormat::Mixed if whole == 0 => write!(f, "{numer}/{denom}"),
+            RatioFormat::Mixed => write!(f, "{whole} {numer}/{denom}"),
+            RatioFormat::Decimal => {
+                write!(f, "{whole}.")?;
+                write_fraction_digits(f, numer, denom)
+            }
+            RatioFormat::Vulgar => {
+                if whole != 0 {
+                    write!(f, "{whole}")?;
+                }
+                match VULGAR_FRACTIONS.iter().find(|(n, d, _)| *n == numer && *d == denom) {
+                    Some((_, _, c)) => f.write_char(*c),
+                    None => {
+                        write_digits(f, numer, &SUPERSCRIPTS)?;
+                        f.write_char('⁄')?;
+                        write_digits(f, denom, &SUBSCRIPTS)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The absolute value of a ratio split into its whole part and a proper fraction.
+struct Parts {
+    negative: bool,
+    whole: u128,
+    numer: u128,
+    denom: u128,
+}
+
+impl Parts {
+    fn of(ratio: Ratio) -> Self {
+        let (abs, denom) = (ratio.numer.unsigned_abs() as u128, ratio.denom as u128);
+        Self {
+            negative: ratio.numer < 0,
+            whole: abs / denom,
+            numer: abs % denom,
+            denom,
+        }
+    }
+}
+
+/// Writes the digits of the proper fraction `numer / denom` after the point,
+/// putting the repeating ones in parentheses.
+fn write_fraction_digits(f: &mut Formatter<'_>, mut numer: u128, denom: u128) -> std::fmt::Result {
+    let mut digits = String::new();
+    let mut remainders = Vec::new();
+    while numer != 0 {
+        if let Some(start) = remainders.iter().position(|r| *r == numer) {
+            return write!(f, "{}({})", &digits[..start], &digits[start..]);
+        }
+        if MAX_DECIMAL_DIGITS <= digits.len() {
+            return write!(f, "{digits}…");
+        }
+        remainders.push(numer);
+        numer *= 10;
+        digits.push(char::from(b'0' + (numer / denom) as u8));
+        numer %= denom;
+    }
+    f.write_str(&digits)
+}
+
+fn write_digits(f: &mut Formatter<'_>, n: u128, glyphs: &[char; 10]) -> std::fmt::Result {
+    for digit in n.to_string().bytes() {
+        f.write_char(glyphs[usize::from(digit - b'0')])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroIsize;
+
+    use crate::ratio::format::RatioFormat;
+    use crate::ratio::Ratio;
+
+    #[test]
+    fn improper() {
+        assert_eq!(ratio(7, 2).display(RatioFormat::Improper).to_string(), "7/2");
+        assert_eq!(Ratio::from(-3).display(RatioFormat::Improper).to_string(), "-3");
+    }
+
+    #[test]
+    fn mixed() {
+        assert_eq!(ratio(7, 2).display(RatioFormat::Mixed).to_string(), "3 1/2");
+        assert_eq!(ratio(-7, 2).display(RatioFormat::Mixed).to_string(), "-3 1/2");
+        assert_eq!(ratio(1, -2).display(RatioFormat::Mixed).to_string(), "-1/2");
+        assert_eq!(Ratio::from(4).display(RatioFormat::Mixed).to_string(), "4");
+        assert_eq!(Ratio::from(0).display(RatioFormat::Mixed).to_string(), "0");
+    }
+
+    #[test]
+    fn decimal() {
+        assert_eq!(ratio(1, 4).display(RatioFormat::Decimal).to_string(), "0.25");
+        assert_eq!(ratio(-7, 2).display(RatioFormat::Decimal).to_string(), "-3.5");
+        assert_eq!(ratio(1, 3).display(RatioFormat::Decimal).to_string(), "0.(3)");
+        assert_eq!(ratio(1, 6).display(RatioFormat::Decimal).to_string(), "0.1(6)");
+        assert_eq!(ratio(22, 7).display(RatioFormat::Decimal).to_string(), "3.(142857)");
+        assert_eq!(Ratio::from(12).display(RatioFormat::Decimal).to_string(), "12");
+        let long = ratio(1, 997).display(RatioFormat::Decimal).to_string();
+        assert!(long.starts_with("0.001003") && long.ends_with('…'));
+    }
+
+    #[test]
+    fn vulgar() {
+        assert_eq!(ratio(1, 2).display(RatioFormat::Vulgar).to_string(), "½");
+        assert_eq!(ratio(-7, 2).display(RatioFormat::Vulgar).to_string(), "-3½");
+        assert_eq!(ratio(11, 12).display(RatioFormat::Vulgar).to_string(), "¹¹⁄₁₂");
+        assert_eq!(Ratio::from(5).display(RatioFormat::Vulgar).to_string(), "5");
+    }
+
+    #[test]
+    fn extremes() {
+        let min = Ratio::from(isize::MIN);
+        assert_eq!(min.display(RatioFormat::Mixed).to_string(), isize::MIN.to_string());
+        let tiny = Ratio::new(-1, NonZeroIsize::new(isize::MAX).unwrap());
+        assert_eq!(tiny.display(RatioFormat::Vulgar).to_string().chars().next(), Some('-'));
+    }
+
+    #[test]
+    fn cycle() {
+        let mut format = RatioFormat::default();
+        for expected in RatioFormat::ALL.iter().cycle().skip(1).take(4) {
+            format = format.next();
+            assert_eq!(format, *expected);
+        }
+    }
+
+    fn ratio(numer: isize, denom: isize) -> Ratio {
+        Ratio::new(numer, NonZeroIsize::new(denom).unwrap())
+    }
+}
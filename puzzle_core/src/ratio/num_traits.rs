@@ -0,0 +1,163 @@
+//! Implements the traits of `num` for [`Ratio`], so it works with code generic over numbers.
+
+use std::num::{IntErrorKind, NonZeroIsize, ParseIntError};
+
+use num::traits::{Num, One, Signed, ToPrimitive, Zero};
+
+use crate::ratio::parse::ParseRatioError;
+use crate::ratio::Ratio;
+
+impl Zero for Ratio {
+    #[inline]
+    fn zero() -> Self {
+        Ratio::ZERO
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.numer == 0
+    }
+}
+
+impl One for Ratio {
+    #[inline]
+    fn one() -> Self {
+        Ratio::ONE
+    }
+
+    #[inline]
+    fn is_one(&self) -> bool {
+        *self == Ratio::ONE
+    }
+}
+
+impl Num for Ratio {
+    type FromStrRadixErr = ParseRatioError;
+
+    /// Parses an integer or a fraction such as `-3/4` in `radix`.
+    ///
+    /// In radix 10, this also accepts everything [`str::parse`] does.
+    /// Fails with [`ParseRatioError::InvalidRadix`] if `radix` isn't between 2 and 36.
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        if !(2..=36).contains(&radix) {
+            return Err(ParseRatioError::InvalidRadix(radix));
+        }
+        if radix == 10 {
+            return str.parse();
+        }
+        let (numer, denom) = str.split_once('/').unwrap_or((str, "1"));
+        let numer = isize::from_str_radix(numer, radix).map_err(radix_error)?;
+        let denom = isize::from_str_radix(denom, radix).map_err(radix_error)?;
+        let denom = NonZeroIsize::new(denom).ok_or(ParseRatioError::ZeroDenominator)?;
+        Ratio::checked_new(numer, denom).map_err(|_| ParseRatioError::Overflow)
+    }
+}
+
+impl Signed for Ratio {
+    /// ## Panics
+    ///
+    /// Panics if the numerator is `isize::MIN`.
+    #[inline]
+    fn abs(&self) -> Self {
+        if self.is_negative() { -*self } else { *self }
+    }
+
+    #[inline]
+    fn abs_sub(&self, other: &Self) -> Self {
+        if self <= other { Ratio::ZERO } else { *self - *other }
+    }
+
+    #[inline]
+    fn signum(&self) -> Self {
+        Ratio::from(self.numer.signum())
+    }
+
+    #[inline]
+    fn is_positive(&self) -> bool {
+        0 < self.numer
+    }
+
+    #[inline]
+    fn is_negative(&self) -> bool {
+        self.numer < 0
+    }
+}
+
+impl ToPrimitive for Ratio {
+    /// Returns the integer part, truncated toward zero.
+    #[inline]
+    fn to_i64(&self) -> Option<i64> {
+        i64::try_from(self.numer / self.denom).ok()
+    }
+
+    /// Returns the integer part, truncated toward zero, if it isn't negative.
+    #[inline]
+    fn to_u64(&self) -> Option<u64> {
+        u64::try_from(self.numer / self.denom).ok()
+    }
+
+    #[inline]
+    fn to_f64(&self) -> Option<f64> {
+        Some(Ratio::to_f64(self))
+    }
+}
+
+fn radix_error(error: ParseIntError) -> ParseRatioError {
+    match error.kind() {
+        IntErrorKind::Empty => ParseRatioError::Malformed,
+        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => ParseRatioError::Overflow,
+        _ => ParseRatioError::InvalidDigit,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroIsize;
+
+    use num::traits::{Num, One, Signed, ToPrimitive, Zero};
+
+    use crate::ratio::parse::ParseRatioError;
+    use crate::ratio::Ratio;
+
+    #[test]
+    fn identities() {
+        assert!(Ratio::zero().is_zero());
+        assert!(Ratio::one().is_one());
+        assert!(!half().is_one());
+    }
+
+    #[test]
+    fn from_str_radix() {
+        assert_eq!(Ratio::from_str_radix("-ff/10", 16), Ok(Ratio::new(-255, NonZeroIsize::new(16).unwrap())));
+        assert_eq!(Ratio::from_str_radix("101", 2), Ok(Ratio::from(5)));
+        assert_eq!(Ratio::from_str_radix("1 1/2", 10), Ok(Ratio::new(3, NonZeroIsize::new(2).unwrap())));
+        assert_eq!(Ratio::from_str_radix("1/0", 2), Err(ParseRatioError::ZeroDenominator));
+        assert_eq!(Ratio::from_str_radix("12", 2), Err(ParseRatioError::InvalidDigit));
+        assert_eq!(Ratio::from_str_radix("1/", 2), Err(ParseRatioError::Malformed));
+        assert_eq!(Ratio::from_str_radix("1", 1), Err(ParseRatioError::InvalidRadix(1)));
+        assert_eq!(Ratio::from_str_radix("1", 37), Err(ParseRatioError::InvalidRadix(37)));
+    }
+
+    #[test]
+    fn signed() {
+        assert_eq!((-half()).abs(), half());
+        assert_eq!(half().signum(), Ratio::one());
+        assert_eq!((-half()).signum(), -Ratio::one());
+        assert_eq!(Ratio::zero().signum(), Ratio::zero());
+        assert!((-half()).is_negative() && half().is_positive());
+        assert_eq!(Ratio::one().abs_sub(&half()), half());
+        assert_eq!(half().abs_sub(&Ratio::one()), Ratio::zero());
+    }
+
+    #[test]
+    fn to_primitive() {
+        let ratio = Ratio::new(-7, NonZeroIsize::new(2).unwrap());
+        assert_eq!(ratio.to_i64(), Some(-3));
+        assert_eq!(ratio.to_u64(), None);
+        assert_eq!(ToPrimitive::to_f64(&ratio), Some(-3.5));
+    }
+
+    fn half() -> Ratio {
+        Ratio::new(1, NonZeroIsize::new(2).unwrap())
+    }
+}
@@ -0,0 +1,178 @@
+//! Parses a [`Ratio`] from text such as `3/4`, `-2`, `1 1/2` or `0.25`.
+
+use std::fmt::{Display, Formatter};
+use std::num::{IntErrorKind, NonZeroIsize};
+use std::str::FromStr;
+
+use crate::ratio::Ratio;
+
+/// The error returned when text isn't a [`Ratio`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ParseRatioError {
+    /// The text is empty or only whitespace.
+    Empty,
+    /// The text isn't one of the accepted forms, such as `1.` or `1 2`.
+    Malformed,
+    /// A part that should be a number has something else in it, such as the `x` of `1/x`.
+    InvalidDigit,
+    ZeroDenominator,
+    /// The value doesn't fit in a [`Ratio`].
+    Overflow,
+    /// The radix given to [`num::Num::from_str_radix`] isn't between 2 and 36.
+    InvalidRadix(u32),
+}
+
+impl Display for ParseRatioError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseRatioError::Empty => f.write_str("the number is empty"),
+            ParseRatioError::Malformed => f.write_str("expected an integer, a fraction, a mixed number or a decimal"),
+            ParseRatioError::InvalidDigit => f.write_str("invalid digit"),
+            ParseRatioError::ZeroDenominator => f.write_str("the denominator is zero"),
+            ParseRatioError::Overflow => f.write_str("the number is too large"),
+            ParseRatioError::InvalidRadix(radix) => write!(f, "radix {radix} is not between 2 and 36"),
+        }
+    }
+}
+
+impl std::error::Error for ParseRatioError {}
+
+impl FromStr for Ratio {
+    type Err = ParseRatioError;
+
+    /// Accepts an integer (`-2`), a fraction (`3/4`), a mixed number (`1 1/2`) or a finite decimal (`0.25`).
+    ///
+    /// Only the whole number of a mixed number and the numerator and denominator of a fraction may have a sign.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseRatioError::Empty);
+        }
+        if let Some((whole, fraction)) = s.split_once(char::is_whitespace) {
+            return parse_mixed(whole, fraction.trim_start());
+        }
+        if let Some((numer, denom)) = s.split_once('/') {
+            return new_ratio(parse_signed(numer)?, parse_signed(denom)?);
+        }
+        if let Some((int, frac)) = s.split_once('.') {
+            return parse_decimal(int, frac);
+        }
+        parse_signed(s).map(Ratio::from)
+    }
+}
+
+/// Parses `1 1/2`; the fraction is added away from zero, so `-1 1/2` is `-3/2`.
+fn parse_mixed(whole: &str, fraction: &str) -> Result<Ratio, ParseRatioError> {
+    let (negative, digits) = split_sign(whole);
+    let whole = parse_digits(digits)?;
+    let (numer, denom) = fraction.split_once('/').ok_or(ParseRatioError::Malformed)?;
+    let (numer, denom) = (parse_digits(numer)?, parse_digits(denom)?);
+    if denom == 0 {
+        return Err(ParseRatioError::ZeroDenominator);
+    }
+    let numer = whole
+        .checked_mul(denom)
+        .and_then(|n| n.checked_add(numer))
+        .ok_or(ParseRatioError::Overflow)?;
+    new_ratio(if negative { -numer } else { numer }, denom)
+}
+
+/// Parses `-0.25` as `int` and `frac` around the point.
+fn parse_decimal(int: &str, frac: &str) -> Result<Ratio, ParseRatioError> {
+    let (negative, int) = split_sign(int);
+    let digits = frac.len() as u32;
+    let int = parse_digits(int)?;
+    let frac = parse_digits(frac)?;
+    let denom = 10isize.checked_pow(digits).ok_or(ParseRatioError::Overflow)?;
+    let numer = int
+        .checked_mul(denom)
+        .and_then(|n| n.checked_add(frac))
+        .ok_or(ParseRatioError::Overflow)?;
+    new_ratio(if negative { -numer } else { numer }, denom)
+}
+
+fn new_ratio(numer: isize, denom: isize) -> Result<Ratio, ParseRatioError> {
+    NonZeroIsize::new(denom)
+        .map(|denom| Ratio::new(numer, denom))
+        .ok_or(ParseRatioError::ZeroDenominator)
+}
+
+fn parse_signed(s: &str) -> Result<isize, ParseRatioError> {
+    let (negative, digits) = split_sign(s);
+    let n = parse_digits(digits)?;
+    Ok(if negative { -n } else { n })
+}
+
+/// Parses ASCII digits without a sign.
+fn parse_digits(s: &str) -> Result<isize, ParseRatioError> {
+    if s.is_empty() {
+        return Err(ParseRatioError::Malformed);
+    }
+    if !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ParseRatioError::InvalidDigit);
+    }
+    s.parse().map_err(|e: std::num::ParseIntError| match e.kind() {
+        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => ParseRatioError::Overflow,
+        _ => ParseRatioError::InvalidDigit,
+    })
+}
+
+fn split_sign(s: &str) -> (bool, &str) {
+    match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroIsize;
+
+    use crate::ratio::parse::ParseRatioError;
+    use crate::ratio::Ratio;
+
+    #[test]
+    fn forms() {
+        assert_eq!("3/4".parse(), Ok(ratio(3, 4)));
+        assert_eq!("6/8".parse(), Ok(ratio(3, 4)));
+        assert_eq!(" -2 ".parse(), Ok(Ratio::from(-2)));
+        assert_eq!("+5".parse(), Ok(Ratio::from(5)));
+        assert_eq!("1 1/2".parse(), Ok(ratio(3, 2)));
+        assert_eq!("-1  1/2".parse(), Ok(ratio(-3, 2)));
+        assert_eq!("0.25".parse(), Ok(ratio(1, 4)));
+        assert_eq!("-1.50".parse(), Ok(ratio(-3, 2)));
+    }
+
+    #[test]
+    fn negative_denominator() {
+        assert_eq!("1/-2".parse(), Ok(ratio(-1, 2)));
+        assert_eq!("-1/-2".parse(), Ok(ratio(1, 2)));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!("".parse::<Ratio>(), Err(ParseRatioError::Empty));
+        assert_eq!("1/2/3".parse::<Ratio>(), Err(ParseRatioError::InvalidDigit));
+        assert_eq!("1.".parse::<Ratio>(), Err(ParseRatioError::Malformed));
+        assert_eq!("1 2".parse::<Ratio>(), Err(ParseRatioError::Malformed));
+        assert_eq!("1 -1/2".parse::<Ratio>(), Err(ParseRatioError::InvalidDigit));
+        assert_eq!("x".parse::<Ratio>(), Err(ParseRatioError::InvalidDigit));
+        assert_eq!("--1".parse::<Ratio>(), Err(ParseRatioError::InvalidDigit));
+        assert_eq!("1/0".parse::<Ratio>(), Err(ParseRatioError::ZeroDenominator));
+        assert_eq!("1 1/0".parse::<Ratio>(), Err(ParseRatioError::ZeroDenominator));
+        assert_eq!("99999999999999999999".parse::<Ratio>(), Err(ParseRatioError::Overflow));
+        assert_eq!("0.00000000000000000001".parse::<Ratio>(), Err(ParseRatioError::Overflow));
+    }
+
+    #[test]
+    fn display_round_trip() {
+        for ratio in [ratio(3, 4), ratio(-7, 2), Ratio::from(0), Ratio::from(-12)] {
+            assert_eq!(ratio.to_string().parse(), Ok(ratio));
+        }
+    }
+
+    fn ratio(numer: isize, denom: isize) -> Ratio {
+        Ratio::new(numer, NonZeroIsize::new(denom).unwrap())
+    }
+}
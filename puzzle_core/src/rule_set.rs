@@ -0,0 +1,92 @@
+//! Which operation each direction performs.
+
+use std::fmt::Write;
+
+use crate::move_dir::MoveDir;
+use crate::operation::Operation;
+
+/// Maps every [`MoveDir`] to the [`Operation`] performed
+/// when a cell moves onto another cell in that direction.
+///
+/// Moving onto an empty cell is always a swap, whatever the rule set says.
+///
+/// ```
+/// use puzzle_core::move_dir::MoveDir;
+/// use puzzle_core::operation::Operation;
+/// use puzzle_core::rule_set::RuleSet;
+///
+/// let rules = RuleSet::CLASSIC.with(MoveDir::Up, Operation::Rem);
+/// assert_eq!(rules.operation(MoveDir::Up), Operation::Rem);
+/// assert_eq!(rules.operation(MoveDir::LeftUp), Operation::Add);
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Reflect))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RuleSet {
+    /// Indexed by `MoveDir as usize`.
+    operations: [Operation; 8],
+}
+
+impl RuleSet {
+    /// The original rules: the diagonals calculate and the straight directions swap.
+    pub const CLASSIC: RuleSet = RuleSet::new(Operation::Swap)
+        .with(MoveDir::LeftUp, Operation::Add)
+        .with(MoveDir::RightUp, Operation::Sub)
+        .with(MoveDir::LeftDown, Operation::Mul)
+        .with(MoveDir::RightDown, Operation::Div);
+
+    /// Creates a rule set in which every direction performs `operation`.
+    #[inline]
+    pub const fn new(operation: Operation) -> Self {
+        Self {
+            operations: [operation; 8]
+        }
+    }
+
+    /// Returns this rule set with `dir` performing `operation`.
+    #[inline]
+    pub const fn with(mut self, dir: MoveDir, operation: Operation) -> Self {
+        self.operations[dir as usize] = operation;
+        self
+    }
+
+    /// Returns the operation performed when a cell moves onto another cell in `dir`.
+    #[inline]
+    pub const fn operation(&self, dir: MoveDir) -> Operation {
+        self.operations[dir as usize]
+    }
+
+    #[inline]
+    pub const fn is_swap(&self, dir: MoveDir) -> bool {
+        self.operation(dir).is_swap()
+    }
+
+    /// Renders the rules of `directions` as the markdown table used in the README.
+    pub fn markdown_table(&self, directions: &[MoveDir]) -> String {
+        let mut table = String::from("| move direction | operation |\n|---|---|\n");
+        for dir in directions {
+            writeln!(table, "| {dir} | {} |", self.operation(*dir).expression()).unwrap();
+        }
+        table
+    }
+}
+
+impl Default for RuleSet {
+    #[inline]
+    fn default() -> Self {
+        Self::CLASSIC
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::move_dir::MoveDir;
+    use crate::rule_set::RuleSet;
+
+    /// The README must describe the rules the game actually uses.
+    #[test]
+    fn readme_rule_table() {
+        let readme = include_str!("../../README.md");
+        assert!(readme.contains(&RuleSet::CLASSIC.markdown_table(&MoveDir::ALL)));
+    }
+}
@@ -0,0 +1,265 @@
+//! Exhaustive search over the legal moves of a [`Stage`].
+//!
+//! Every branch is expanded with [`Stage::legal_moves`],
+//! so the solver walks exactly the same moves as the player and the answer generator.
+
+use std::collections::{HashSet, VecDeque};
+
+use crate::answer::steps::Steps;
+use crate::calculator::Calculator;
+use crate::canonical::distinct;
+use crate::move_dir::MoveDir;
+use crate::number::PuzzleNumber;
+use crate::stage::{Stage, StageRatioArray};
+
+/// The result of searching every branch from a stage.
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct SolutionSpace {
+    /// All move sequences that reach the goal.
+    pub solutions: Vec<Steps>,
+    /// The number of branches that run out of legal moves without reaching the goal.
+    pub dead_ends: usize,
+}
+
+/// Returns all move sequences that leave only the `goal` cell on the stage.
+///
+/// The search is depth-first and runs from the current state of `stage`,
+/// so moved flags and empty cells are respected.
+pub fn solve<const STAGE_SIZE: usize, Calc, Num>(
+    stage: &Stage<STAGE_SIZE, Calc, Num>,
+    goal: Num,
+) -> Vec<Steps>
+    where Calc: Calculator + Clone + 'static,
+          Num: PuzzleNumber
+{
+    explore(stage, goal).solutions
+}
+
+/// Same as [`solve`], but keeps only one of the solutions that are the same written differently.
+///
+/// See [`crate::canonical`] for which solutions count as the same.
+pub fn solve_distinct<const STAGE_SIZE: usize, Calc, Num>(
+    stage: &Stage<STAGE_SIZE, Calc, Num>,
+    goal: Num,
+) -> Vec<Steps>
+    where Calc: Calculator + Clone + 'static,
+          Num: PuzzleNumber
+{
+    distinct(stage, solve(stage, goal))
+}
+
+/// Searches every branch from `stage` and collects both the solutions and the dead ends.
+pub fn explore<const STAGE_SIZE: usize, Calc, Num>(
+    stage: &Stage<STAGE_SIZE, Calc, Num>,
+    goal: Num,
+) -> SolutionSpace
+    where Calc: Calculator + Clone + 'static,
+          Num: PuzzleNumber
+{
+    explore_within(stage, goal, usize::MAX).expect("an unlimited search never gives up")
+}
+
+/// Same as [`explore`], but gives up and returns `None`
+/// once more than `max_states` states have been visited.
+///
+/// The number of branches grows exponentially with the number of cells,
+/// so this should be used for boards larger than the diamond.
+pub fn explore_within<const STAGE_SIZE: usize, Calc, Num>(
+    stage: &Stage<STAGE_SIZE, Calc, Num>,
+    goal: Num,
+    max_states: usize,
+) -> Option<SolutionSpace>
+    where Calc: Calculator + Clone + 'static,
+          Num: PuzzleNumber
+{
+    let mut space = SolutionSpace::default();
+    let mut budget = max_states;
+    search(stage, &goal, &mut Vec::new(), &mut space, &mut budget)?;
+    Some(space)
+}
+
+/// Returns one of the shortest move sequences that leave only the `goal` cell on the stage.
+///
+/// The search is breadth-first and tries moves in the order of [`Stage::legal_moves`],
+/// so the result is always the same for the same state.
+pub fn shortest<const STAGE_SIZE: usize, Calc, Num>(
+    stage: &Stage<STAGE_SIZE, Calc, Num>,
+    goal: Num,
+) -> Option<Steps>
+    where Calc: Calculator + Clone + 'static,
+          Num: PuzzleNumber
+{
+    let mut visited = HashSet::from([stage.movable_ratios().clone()]);
+    let mut queue = VecDeque::from([(stage.clone(), Vec::new())]);
+
+    while let Some((stage, path)) = queue.pop_front() {
+        if stage.last_ratio().as_ref() == Some(&goal) {
+            return Some(path.into_iter().collect());
+        }
+        for (cell_no, dir) in stage.legal_moves() {
+            let mut next = stage.clone();
+            if next.move_cell(cell_no, dir).is_ok() && visited.insert(next.movable_ratios().clone()) {
+                let mut path = path.clone();
+                path.push((cell_no, dir));
+                queue.push_back((next, path));
+            }
+        }
+    }
+    None
+}
+
+/// Returns whether `goal` can still be reached from the current state of `stage`.
+///
+/// This stops at the first solution found and never visits the same state twice,
+/// so it is cheap enough to call after every move.
+pub fn is_reachable<const STAGE_SIZE: usize, Calc, Num>(
+    stage: &Stage<STAGE_SIZE, Calc, Num>,
+    goal: Num,
+) -> bool
+    where Calc: Calculator + Clone + 'static,
+          Num: PuzzleNumber
+{
+    reachable(stage, &goal, &mut HashSet::new())
+}
+
+fn reachable<const STAGE_SIZE: usize, Calc, Num>(
+    stage: &Stage<STAGE_SIZE, Calc, Num>,
+    goal: &Num,
+    visited: &mut HashSet<StageRatioArray<STAGE_SIZE, Num>>,
+) -> bool
+    where Calc: Calculator + Clone + 'static,
+          Num: PuzzleNumber
+{
+    if stage.last_ratio().as_ref() == Some(goal) {
+        return true;
+    }
+
+    stage
+        .legal_moves()
+        .into_iter()
+        .any(|(cell_no, dir)| {
+            let mut next = stage.clone();
+            next.move_cell(cell_no, dir).is_ok()
+                && visited.insert(next.movable_ratios().clone())
+                && reachable(&next, goal, visited)
+        })
+}
+
+fn search<const STAGE_SIZE: usize, Calc, Num>(
+    stage: &Stage<STAGE_SIZE, Calc, Num>,
+    goal: &Num,
+    path: &mut Vec<(usize, MoveDir)>,
+    space: &mut SolutionSpace,
+    budget: &mut usize,
+) -> Option<()>
+    where Calc: Calculator + Clone + 'static,
+          Num: PuzzleNumber
+{
+    *budget = budget.checked_sub(1)?;
+    if stage.last_ratio().as_ref() == Some(goal) {
+        space.solutions.push(path.iter().copied().collect());
+        return Some(());
+    }
+
+    let moves = stage.legal_moves();
+    if moves.is_empty() {
+        space.dead_ends += 1;
+        return Some(());
+    }
+
+    for (cell_no, dir) in moves {
+        let mut next = stage.clone();
+        if next.move_cell(cell_no, dir).is_err() {
+            continue;
+        }
+        path.push((cell_no, dir));
+        search(&next, goal, path, space, budget)?;
+        path.pop();
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answer::AnswerInfo;
+    use crate::calculator::small_size::SmallSizeCalculator;
+    use crate::move_dir::MoveDir;
+    use crate::ratio::Ratio;
+    use crate::solver::{explore, explore_within, shortest, solve, solve_distinct};
+    use crate::stage::Stage;
+
+    #[test]
+    fn legal_moves_contain_down() {
+        let stage = stage();
+        assert!(stage.legal_moves().contains(&(1, MoveDir::Down)));
+    }
+
+    #[test]
+    fn all_solutions_reach_goal() {
+        let stage = stage();
+        let solutions = solve(&stage, Ratio::from(10));
+        assert!(!solutions.is_empty());
+
+        for steps in solutions {
+            assert_eq!(steps.verify(&stage, Ratio::from(10)), Ok(Ratio::from(10)));
+        }
+    }
+
+    #[test]
+    fn contains_generated_answer() {
+        let stage = stage();
+        let answer = AnswerInfo::generate::<4, SmallSizeCalculator>([1, 2, 3, 4].map(Ratio::from)).unwrap();
+        assert!(solve(&stage, answer.ratio).contains(&answer.steps));
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let space = explore(&stage(), Ratio::from(1000));
+        assert!(space.solutions.is_empty());
+        assert!(0 < space.dead_ends);
+    }
+
+    #[test]
+    fn solve_from_current_state() {
+        let mut stage = stage();
+        stage.move_cell(2, MoveDir::LeftUp).unwrap();
+        // [4, 2, _, 4]
+        assert!(solve(&stage, Ratio::from(10)).is_empty());
+        let solutions = solve(&stage, Ratio::from(24));
+        assert!(!solutions.is_empty());
+        assert!(solutions.iter().all(|steps| !steps.is_empty()));
+    }
+
+    #[test]
+    fn distinct_solutions() {
+        let stage = stage();
+        let distinct = solve_distinct(&stage, Ratio::from(10));
+        let all = solve(&stage, Ratio::from(10));
+        assert!(distinct.len() < all.len());
+        assert!(distinct.iter().all(|steps| all.contains(steps)));
+    }
+
+    #[test]
+    fn give_up_exploring() {
+        let stage = stage();
+        assert_eq!(explore_within(&stage, Ratio::from(10), 10), None);
+        assert_eq!(explore_within(&stage, Ratio::from(10), usize::MAX), Some(explore(&stage, Ratio::from(10))));
+    }
+
+    #[test]
+    fn shortest_is_minimum() {
+        let stage = stage();
+        let min = solve(&stage, Ratio::from(10))
+            .iter()
+            .map(|steps| steps.len())
+            .min();
+        assert_eq!(shortest(&stage, Ratio::from(10)).map(|steps| steps.len()), min);
+        assert_eq!(shortest(&stage, Ratio::from(1000)), None);
+    }
+
+    fn stage() -> Stage<4, SmallSizeCalculator> {
+        Stage::from([
+            1, 2, 3, 4,
+        ])
+    }
+}
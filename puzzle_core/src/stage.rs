@@ -1,52 +1,81 @@
 use std::ops::Index;
 
-use num::FromPrimitive;
-
 use crate::calculator::Calculator;
 use crate::movable_ratio::MovableRatio;
 use crate::move_dir::MoveDir;
+use crate::number::PuzzleNumber;
+use crate::operation::Operation;
 use crate::ratio::Ratio;
+use crate::rule_set::RuleSet;
+use crate::solver;
+use crate::stage::history::{MoveKind, MoveRecord};
+use crate::stage::outcome::{MoveError, MoveOutcome};
+use crate::topology::Topology;
+
+pub mod history;
+pub mod outcome;
+pub mod snapshot;
 
-pub type RatioArray<const STAGE_SIZE: usize> = [Ratio; STAGE_SIZE];
+pub type RatioArray<const STAGE_SIZE: usize, Num = Ratio> = [Num; STAGE_SIZE];
 
-pub type StageRatioArray<const STAGE_SIZE: usize> = [Option<MovableRatio>; STAGE_SIZE];
+pub type StageRatioArray<const STAGE_SIZE: usize, Num = Ratio> = [Option<MovableRatio<Num>>; STAGE_SIZE];
 
 
-#[derive(Debug)]
-pub struct Stage<const STAGE_SIZE: usize, Calc: Calculator> {
-    ratios: StageRatioArray<STAGE_SIZE>,
-    cache_undo: Vec<StageRatioArray<STAGE_SIZE>>,
-    cache_redo: Vec<StageRatioArray<STAGE_SIZE>>,
+/// A board of `STAGE_SIZE` cells holding values of `Num`, which is [`Ratio`] unless stated otherwise.
+#[derive(Debug, Clone)]
+pub struct Stage<const STAGE_SIZE: usize, Calc: Calculator, Num: PuzzleNumber = Ratio> {
+    ratios: StageRatioArray<STAGE_SIZE, Num>,
+    initial: StageRatioArray<STAGE_SIZE, Num>,
+    /// Includes the moves that can be redone after `position`.
+    history: Vec<MoveRecord<Num>>,
+    position: usize,
     calculator: Calc,
+    rules: RuleSet,
 }
 
 
-impl<const STAGE_SIZE: usize, Calc> Stage<STAGE_SIZE, Calc>
-    where Calc: Calculator + 'static
+impl<const STAGE_SIZE: usize, Calc, Num> Stage<STAGE_SIZE, Calc, Num>
+    where Calc: Calculator + 'static,
+          Num: PuzzleNumber
 {
+    /// Creates a stage played with [`RuleSet::CLASSIC`].
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `STAGE_SIZE` differs from the number of cells of the calculator's topology.
     #[inline]
-    pub fn new(calculator: Calc, ratios: RatioArray<STAGE_SIZE>) -> Self {
+    pub fn new(calculator: Calc, ratios: RatioArray<STAGE_SIZE, Num>) -> Self {
+        Self::with_rules(calculator, RuleSet::CLASSIC, ratios)
+    }
+
+    /// ## Panics
+    ///
+    /// Panics if `STAGE_SIZE` differs from the number of cells of the calculator's topology.
+    pub fn with_rules(calculator: Calc, rules: RuleSet, ratios: RatioArray<STAGE_SIZE, Num>) -> Self {
+        assert_eq!(calculator.topology().cell_count(), STAGE_SIZE, "the stage size must match the topology");
+        let ratios = ratios.map(|r| Some(MovableRatio::from(r)));
         Self {
-            ratios: ratios.map(|r| Some(MovableRatio::from(r))),
+            initial: ratios.clone(),
+            ratios,
+            history: Vec::new(),
+            position: 0,
             calculator,
-            cache_undo: Vec::new(),
-            cache_redo: Vec::new(),
+            rules,
         }
     }
 
-    pub fn last_ratio(&self) -> Option<Ratio> {
-        if self.ratios.iter().any(|r| { r.is_some_and(|r| r.moved) }) {
+    pub fn last_ratio(&self) -> Option<Num> {
+        if self.ratios.iter().any(|r| { r.as_ref().is_some_and(|r| r.moved) }) {
             return None;
         }
 
         let ratios = self.ratios
             .iter()
-            .copied()
             .flatten()
             .filter(|r| !r.moved)
             .collect::<Vec<_>>();
         if ratios.len() == 1 {
-            Some(ratios[0].ratio)
+            Some(ratios[0].ratio.clone())
         } else {
             None
         }
@@ -57,7 +86,7 @@ impl<const STAGE_SIZE: usize, Calc> Stage<STAGE_SIZE, Calc>
             .iter()
             .enumerate()
             .filter_map(|(i, r)| {
-                r.is_some_and(|r| !r.moved).then_some(i)
+                r.as_ref().is_some_and(|r| !r.moved).then_some(i)
             })
             .collect()
     }
@@ -67,134 +96,173 @@ impl<const STAGE_SIZE: usize, Calc> Stage<STAGE_SIZE, Calc>
             .iter()
             .all(|r| {
                 r
+                    .as_ref()
                     .map(|r| r.moved)
                     .unwrap_or(true)
             })
     }
 
     pub fn can_move(&self, src: usize, dir: MoveDir) -> bool {
-        self.calculator.can_move(&self.ratios, src, dir)
+        self.calculator.can_move(&self.rules, &self.ratios, src, dir)
     }
 
     pub fn movable_dirs(&self, src: usize) -> Vec<MoveDir> {
-        let Some(MovableRatio { moved: _m @ false, .. }) = self.ratios[src] else {
+        let Some(MovableRatio { moved: _m @ false, .. }) = &self.ratios[src] else {
             return Vec::with_capacity(0);
         };
-        let mut moves = Vec::with_capacity(8);
-        let mut push = |dir: MoveDir| {
-            if self.can_move(src, dir) {
-                moves.push(dir);
-            }
-        };
-        push(MoveDir::LeftUp);
-        push(MoveDir::Up);
-        push(MoveDir::RightUp);
-        push(MoveDir::Left);
-        push(MoveDir::Right);
-        push(MoveDir::LeftDown);
-        push(MoveDir::RightDown);
+        self.topology()
+            .directions()
+            .iter()
+            .copied()
+            .filter(|dir| self.can_move(src, *dir))
+            .collect()
+    }
+
+    /// Returns every legal move on this stage as `(cell_no, dir)` pairs.
+    ///
+    /// This is the only move generator; the answer generator and [`crate::solver`] are built on it.
+    pub fn legal_moves(&self) -> Vec<(usize, MoveDir)> {
+        self.movable_indices()
+            .into_iter()
+            .flat_map(|src| {
+                self.movable_dirs(src)
+                    .into_iter()
+                    .map(move |dir| (src, dir))
+            })
+            .collect()
+    }
+
+    /// Returns the operation that moving `src` to `dir` performs,
+    /// or `None` if the move is not allowed.
+    ///
+    /// Moving onto an empty cell is always a [`Operation::Swap`].
+    pub fn operation(&self, src: usize, dir: MoveDir) -> Option<Operation> {
+        if !self.can_move(src, dir) {
+            return None;
+        }
+        let dist_no = self.dist_no(src, dir)?;
+        if self.ratios[dist_no].is_some() {
+            Some(self.rules.operation(dir))
+        } else {
+            Some(Operation::Swap)
+        }
+    }
 
-        moves
+    /// Returns the cell reached by moving `src` to `dir`, or `None` if it is off the board.
+    #[inline]
+    pub fn dist_no(&self, src: usize, dir: MoveDir) -> Option<usize> {
+        self.calculator.dist_no(src, dir)
     }
 
-    pub fn move_dist(&self, src: usize, dir: MoveDir) -> Option<&Option<MovableRatio>> {
-        let index = usize::from_isize(src as isize + dir as isize)?;
-        self.ratios.get(index)
+    pub fn move_dist(&self, src: usize, dir: MoveDir) -> Option<&Option<MovableRatio<Num>>> {
+        self.ratios.get(self.dist_no(src, dir)?)
     }
 
-    pub fn movable_ratios(&self) -> &[Option<MovableRatio>; STAGE_SIZE] {
+    #[inline]
+    pub const fn rules(&self) -> &RuleSet {
+        &self.rules
+    }
+
+    #[inline]
+    pub fn topology(&self) -> &Calc::Topology {
+        self.calculator.topology()
+    }
+
+    pub fn movable_ratios(&self) -> &StageRatioArray<STAGE_SIZE, Num> {
         &self.ratios
     }
 
-    pub fn ratios(&self) -> [Option<Ratio>; STAGE_SIZE] {
-        self.ratios.map(|r| r.map(|r| r.ratio))
+    pub fn ratios(&self) -> [Option<Num>; STAGE_SIZE] {
+        self.ratios.clone().map(|r| r.map(|r| r.ratio))
     }
 
     pub fn undo(&mut self) {
-        if let Some(cache) = self.cache_undo.pop() {
-            self.cache_redo.push(self.ratios);
-            self.ratios = cache;
+        if let Some(position) = self.position.checked_sub(1) {
+            self.jump_to(position);
         }
     }
 
     pub fn redo(&mut self) {
-        if let Some(cache) = self.cache_redo.pop(){
-            self.cache_undo.push(self.ratios);
-            self.ratios = cache;
+        if let Some(record) = self.history.get(self.position) {
+            record.replay(&mut self.ratios);
+            self.position += 1;
         }
     }
 
-    pub fn move_cell(&mut self, src_no: usize, dir: MoveDir) {
+    /// Moves `src_no` to `dir` if the move is allowed.
+    ///
+    /// A move that isn't allowed, including one whose calculation would overflow,
+    /// leaves the stage untouched and returns the reason.
+    pub fn move_cell(&mut self, src_no: usize, dir: MoveDir) -> Result<MoveOutcome<Num>, MoveError> {
+        let record = self.plan_move(src_no, dir)?;
+        // Calculators may forbid more moves than the rules do.
         if !self.can_move(src_no, dir) {
-            return;
-        }
-        self.cache_undo.push(self.ratios);
-        self.cache_redo.clear();
-        let dist_no = Calc::dist_no::<STAGE_SIZE>(src_no, &dir).unwrap();
-        let src_ratio = self.ratios[src_no].map(|m| m.ratio).unwrap();
-        match dir {
-            // add
-            MoveDir::LeftUp => {
-                self.execute_mov(src_no, dist_no, src_ratio, |d, s| Some(d + s));
-            }
-            // sub
-            MoveDir::RightUp => {
-                self.execute_mov(src_no, dist_no, src_ratio, |d, s| Some(d - s));
-            }
-            // div
-            MoveDir::RightDown => {
-                self.execute_mov(src_no, dist_no, src_ratio, |d, s| d / s);
-            }
-            // mul
-            MoveDir::LeftDown => {
-                self.execute_mov(src_no, dist_no, src_ratio, |d, s| Some(d * s));
-            }
-            // swap
-            _ => {
-                self.swap(src_no, dist_no)
-            }
+            return Err(MoveError::NotAllowed);
         }
+        record.replay(&mut self.ratios);
+        let outcome = MoveOutcome::from(&record);
+        self.history.truncate(self.position);
+        self.history.push(record);
+        self.position += 1;
+        Ok(outcome)
     }
 
-    fn execute_mov(
-        &mut self,
-        src: usize,
-        dest: usize,
-        src_ratio: Ratio,
-        calc: impl FnOnce(Ratio, Ratio) -> Option<Ratio>,
-    ) {
-        if let Some(MovableRatio { ratio: dest_ratio, .. }) = self.ratios[dest] {
-            if let Some(ratio) = calc(dest_ratio, src_ratio) {
-                self.ratios[dest] = Some(MovableRatio::from(ratio));
-                self.ratios[src] = None;
+    fn plan_move(&self, src_no: usize, dir: MoveDir) -> Result<MoveRecord<Num>, MoveError> {
+        let src_ratio = match self.ratios.get(src_no).ok_or(MoveError::OffBoard)? {
+            None => return Err(MoveError::EmptySource),
+            Some(MovableRatio { moved: true, .. }) => return Err(MoveError::AlreadyMoved),
+            Some(MovableRatio { ratio, .. }) => ratio.clone(),
+        };
+        let dist_no = self.dist_no(src_no, dir).ok_or(MoveError::OffBoard)?;
+        let dist_ratio = self.ratios.get(dist_no).ok_or(MoveError::OffBoard)?.as_ref().map(|m| m.ratio.clone());
+        let (operation, result) = match dist_ratio {
+            Some(ref dist_ratio) if !self.rules.is_swap(dir) => {
+                let operation = self.rules.operation(dir);
+                (operation, Some(operation.apply(dist_ratio.clone(), src_ratio.clone())?))
             }
-        } else {
-            self.swap(src, dest);
-        }
+            _ => (Operation::Swap, None)
+        };
+        Ok(MoveRecord {
+            src_no,
+            dist_no,
+            dir,
+            kind: if result.is_some() { MoveKind::Combine } else { MoveKind::Swap },
+            operation,
+            src_ratio,
+            dist_ratio,
+            result,
+        })
     }
+}
 
-    fn swap(&mut self, src: usize, dest: usize) {
-        self.ratios.swap(src, dest);
-        if let Some(d) = self.ratios[dest].as_mut() {
-            d.moved = true;
-        }
+impl<const STAGE_SIZE: usize, Calc, Num> Stage<STAGE_SIZE, Calc, Num>
+    where Calc: Calculator + Clone + 'static,
+          Num: PuzzleNumber
+{
+    /// Returns whether `goal` can still be reached from the current state.
+    ///
+    /// Unlike [`Stage::failed`], this reports a dead stage as soon as the move that made it dead.
+    pub fn is_reachable(&self, goal: Num) -> bool {
+        !self.failed() && solver::is_reachable(self, goal)
     }
 }
 
-impl<const STAGE_SIZE: usize, Calc: Calculator + Default> Default for Stage<STAGE_SIZE, Calc> {
+impl<const STAGE_SIZE: usize, Calc: Calculator + Default, Num: PuzzleNumber> Default for Stage<STAGE_SIZE, Calc, Num> {
     fn default() -> Self {
         Self {
-            ratios: [None; STAGE_SIZE],
+            ratios: std::array::from_fn(|_| None),
+            initial: std::array::from_fn(|_| None),
+            history: Vec::new(),
+            position: 0,
             calculator: Calc::default(),
-            cache_undo: Vec::new(),
-            cache_redo: Vec::new(),
+            rules: RuleSet::CLASSIC,
         }
     }
 }
 
-impl<const STAGE_SIZE: usize, Calc: Calculator + Default + 'static> From<RatioArray<STAGE_SIZE>> for Stage<STAGE_SIZE, Calc> {
+impl<const STAGE_SIZE: usize, Calc: Calculator + Default + 'static, Num: PuzzleNumber> From<RatioArray<STAGE_SIZE, Num>> for Stage<STAGE_SIZE, Calc, Num> {
     #[inline]
-    fn from(ratios: RatioArray<STAGE_SIZE>) -> Self {
+    fn from(ratios: RatioArray<STAGE_SIZE, Num>) -> Self {
         Self::new(Calc::default(), ratios)
     }
 }
@@ -206,8 +274,8 @@ impl<const STAGE_SIZE: usize, Calc: Calculator + Default + 'static> From<[isize;
     }
 }
 
-impl<const STAGE_SIZE: usize, Calc: Calculator + 'static> Index<usize> for Stage<STAGE_SIZE, Calc> {
-    type Output = Option<MovableRatio>;
+impl<const STAGE_SIZE: usize, Calc: Calculator + 'static, Num: PuzzleNumber> Index<usize> for Stage<STAGE_SIZE, Calc, Num> {
+    type Output = Option<MovableRatio<Num>>;
 
     fn index(&self, index: usize) -> &Self::Output {
         &self.ratios[index]
@@ -221,13 +289,16 @@ mod tests {
     use crate::calculator::small_size::SmallSizeCalculator;
     use crate::movable_ratio::MovableRatio;
     use crate::move_dir::MoveDir;
+    use crate::operation::Operation;
     use crate::ratio::Ratio;
+    use crate::rule_set::RuleSet;
+    use crate::stage::outcome::{MoveError, MoveOutcome};
     use crate::stage::Stage;
 
     #[test]
     fn swap_left() {
         let mut stage = stage();
-        stage.move_cell(3, MoveDir::Left);
+        stage.move_cell(3, MoveDir::Left).unwrap();
         assert_eq!(stage[3], Some(MovableRatio::from(1)));
         assert_eq!(stage[0], Some(MovableRatio::new_moved(Ratio::from(4))));
     }
@@ -235,7 +306,7 @@ mod tests {
     #[test]
     fn swap_up() {
         let mut stage = stage();
-        stage.move_cell(2, MoveDir::Up);
+        stage.move_cell(2, MoveDir::Up).unwrap();
         assert_eq!(stage[2], Some(MovableRatio::from(2)));
         assert_eq!(stage[1], Some(MovableRatio::new_moved(Ratio::from(3))));
     }
@@ -244,7 +315,7 @@ mod tests {
     #[test]
     fn swap_right() {
         let mut stage = stage();
-        stage.move_cell(0, MoveDir::Right);
+        stage.move_cell(0, MoveDir::Right).unwrap();
         assert_eq!(stage[0], Some(MovableRatio::from(4)));
         assert_eq!(stage[3], Some(MovableRatio::new_moved(Ratio::from(1))));
     }
@@ -252,7 +323,7 @@ mod tests {
     #[test]
     fn swap_down() {
         let mut stage = stage();
-        stage.move_cell(1, MoveDir::Down);
+        stage.move_cell(1, MoveDir::Down).unwrap();
         assert_eq!(stage[1], Some(MovableRatio::from(3)));
         assert_eq!(stage[2], Some(MovableRatio::new_moved(Ratio::from(2))));
     }
@@ -260,7 +331,7 @@ mod tests {
     #[test]
     fn add() {
         let mut stage = stage();
-        stage.move_cell(2, MoveDir::LeftUp);
+        stage.move_cell(2, MoveDir::LeftUp).unwrap();
         assert_eq!(stage.ratios[2], None);
         assert_eq!(stage.ratios[0], Some(MovableRatio::from(Ratio::from(4))));
     }
@@ -268,7 +339,7 @@ mod tests {
     #[test]
     fn sub() {
         let mut stage = stage();
-        stage.move_cell(0, MoveDir::RightUp);
+        stage.move_cell(0, MoveDir::RightUp).unwrap();
         assert_eq!(stage.ratios[0], None);
         assert_eq!(stage.ratios[1], Some(MovableRatio::from(Ratio::from(1))));
     }
@@ -276,7 +347,7 @@ mod tests {
     #[test]
     fn mul() {
         let mut stage = stage();
-        stage.move_cell(1, MoveDir::LeftDown);
+        stage.move_cell(1, MoveDir::LeftDown).unwrap();
         assert_eq!(stage.ratios[0], Some(MovableRatio::from(Ratio::from(2))));
         assert_eq!(stage.ratios[1], None);
     }
@@ -284,9 +355,111 @@ mod tests {
     #[test]
     fn div() {
         let mut stage = stage();
-        stage.move_cell(1, MoveDir::RightDown);
+        stage.move_cell(1, MoveDir::RightDown).unwrap();
         assert_eq!(stage.ratios[1], None);
-        assert_eq!(stage.ratios[3], Some(MovableRatio::from(Ratio::new(1, NonZeroIsize::new(2).unwrap()))));
+        // 4 / 2
+        assert_eq!(stage.ratios[3], Some(MovableRatio::from(Ratio::from(2))));
+    }
+
+    #[test]
+    fn reachable() {
+        let mut stage = stage();
+        assert!(stage.is_reachable(Ratio::from(10)));
+        stage.move_cell(2, MoveDir::LeftUp).unwrap();
+        assert!(!stage.is_reachable(Ratio::from(10)));
+        assert!(stage.is_reachable(Ratio::from(24)));
+        assert!(!stage.failed());
+    }
+
+    #[test]
+    fn unreachable_if_failed() {
+        let mut stage = stage();
+        stage.move_cell(0, MoveDir::Right).unwrap();
+        stage.move_cell(1, MoveDir::Down).unwrap();
+        stage.move_cell(0, MoveDir::Right).unwrap();
+        stage.move_cell(1, MoveDir::Down).unwrap();
+        assert!(stage.failed());
+        assert!(!stage.is_reachable(Ratio::from(4)));
+    }
+
+    #[test]
+    fn overflow_is_not_allowed() {
+        let mut stage = Stage::<4, SmallSizeCalculator>::from([
+            isize::MAX, 2, 3, 4,
+        ]);
+        // isize::MAX * 2
+        assert!(!stage.can_move(1, MoveDir::LeftDown));
+        assert!(!stage.legal_moves().contains(&(1, MoveDir::LeftDown)));
+        assert_eq!(stage.move_cell(1, MoveDir::LeftDown), Err(MoveError::Overflow));
+        assert_eq!(stage.ratios(), [isize::MAX, 2, 3, 4].map(|r| Some(Ratio::from(r))));
+        // Nothing has been moved, so there is nothing to undo either.
+        stage.undo();
+        assert_eq!(stage[1], Some(MovableRatio::from(2)));
+        // isize::MAX + 3 overflows, but 3 / isize::MAX doesn't.
+        assert!(!stage.can_move(2, MoveDir::LeftUp));
+        stage.move_cell(0, MoveDir::RightDown).unwrap();
+        assert_eq!(stage[2], Some(MovableRatio::from(Ratio::new(3, NonZeroIsize::new(isize::MAX).unwrap()))));
+    }
+
+    #[test]
+    fn min_cells() {
+        let mut stage = Stage::<4, SmallSizeCalculator>::from([
+            -1, isize::MIN, 3, isize::MIN,
+        ]);
+        // -1 * isize::MIN
+        assert!(!stage.can_move(1, MoveDir::LeftDown));
+        assert_eq!(stage.move_cell(1, MoveDir::LeftDown), Err(MoveError::Overflow));
+        // isize::MIN / isize::MIN
+        assert!(stage.can_move(1, MoveDir::RightDown));
+        stage.move_cell(1, MoveDir::RightDown).unwrap();
+        assert_eq!(stage[3], Some(MovableRatio::from(1)));
+    }
+
+    #[test]
+    fn move_outcome() {
+        let mut stage = stage();
+        assert_eq!(stage.move_cell(2, MoveDir::LeftUp), Ok(MoveOutcome::Combine {
+            src_no: 2,
+            dist_no: 0,
+            result: Ratio::from(4),
+        }));
+        assert_eq!(stage.move_cell(1, MoveDir::Down), Ok(MoveOutcome::Swap {
+            src_no: 1,
+            dist_no: 2,
+        }));
+    }
+
+    #[test]
+    fn move_error() {
+        let mut stage = stage();
+        assert_eq!(stage.move_cell(0, MoveDir::Left), Err(MoveError::OffBoard));
+        assert_eq!(stage.move_cell(4, MoveDir::Left), Err(MoveError::OffBoard));
+        stage.move_cell(2, MoveDir::LeftUp).unwrap();
+        assert_eq!(stage.move_cell(2, MoveDir::Up), Err(MoveError::EmptySource));
+        stage.move_cell(1, MoveDir::Down).unwrap();
+        assert_eq!(stage.move_cell(2, MoveDir::Up), Err(MoveError::AlreadyMoved));
+
+        let mut stage = Stage::<4, SmallSizeCalculator>::from([
+            1, 0, 3, 4,
+        ]);
+        // 4 / 0
+        assert_eq!(stage.move_cell(1, MoveDir::RightDown), Err(MoveError::DivisionByZero));
+        assert_eq!(stage.position(), 0);
+    }
+
+    #[test]
+    fn custom_rules() {
+        let rules = RuleSet::CLASSIC
+            .with(MoveDir::LeftUp, Operation::Concat)
+            .with(MoveDir::Down, Operation::Max);
+        let mut stage = Stage::with_rules(SmallSizeCalculator, rules, [1, 2, 3, 4].map(Ratio::from));
+        assert_eq!(stage.operation(2, MoveDir::LeftUp), Some(Operation::Concat));
+        // 1 .. 3
+        stage.move_cell(2, MoveDir::LeftUp).unwrap();
+        assert_eq!(stage[0], Some(MovableRatio::from(13)));
+        assert_eq!(stage[2], None);
+        // Moving onto an empty cell still swaps.
+        assert_eq!(stage.operation(1, MoveDir::Down), Some(Operation::Swap));
     }
 
     fn stage() -> Stage<4, SmallSizeCalculator> {
@@ -0,0 +1,187 @@
+//! The moves played on a [`Stage`], recorded as they happened.
+
+use crate::answer::steps::Steps;
+use crate::calculator::Calculator;
+use crate::movable_ratio::MovableRatio;
+use crate::move_dir::MoveDir;
+use crate::number::PuzzleNumber;
+use crate::operation::Operation;
+use crate::ratio::Ratio;
+use crate::stage::{Stage, StageRatioArray};
+
+/// Whether a move exchanged two cells or merged them into one.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MoveKind {
+    Swap,
+    Combine,
+}
+
+/// A move played on a [`Stage`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MoveRecord<Num = Ratio> {
+    pub src_no: usize,
+    pub dist_no: usize,
+    pub dir: MoveDir,
+    pub kind: MoveKind,
+    /// [`Operation::Swap`] for every swap, including a move onto an empty cell.
+    pub operation: Operation,
+    pub src_ratio: Num,
+    /// `None` if the destination was empty.
+    pub dist_ratio: Option<Num>,
+    /// The value left on the destination by a combine; `None` for a swap.
+    pub result: Option<Num>,
+}
+
+impl<Num: PuzzleNumber> MoveRecord<Num> {
+    /// Plays this move on `ratios` again, without checking whether it is allowed.
+    pub(crate) fn replay<const STAGE_SIZE: usize>(&self, ratios: &mut StageRatioArray<STAGE_SIZE, Num>) {
+        match &self.result {
+            Some(result) => {
+                ratios[self.dist_no] = Some(MovableRatio::from(result.clone()));
+                ratios[self.src_no] = None;
+            }
+            None => {
+                ratios.swap(self.src_no, self.dist_no);
+                if let Some(d) = ratios[self.dist_no].as_mut() {
+                    d.moved = true;
+                }
+            }
+        }
+    }
+}
+
+impl<const STAGE_SIZE: usize, Calc, Num> Stage<STAGE_SIZE, Calc, Num>
+    where Calc: Calculator + 'static,
+          Num: PuzzleNumber
+{
+    /// Returns the moves that led to the current state, oldest first.
+    #[inline]
+    pub fn history(&self) -> &[MoveRecord<Num>] {
+        &self.history[..self.position]
+    }
+
+    /// Returns the moves that can be redone, the next one first.
+    #[inline]
+    pub fn redo_history(&self) -> &[MoveRecord<Num>] {
+        &self.history[self.position..]
+    }
+
+    /// Returns the number of moves that led to the current state.
+    #[inline]
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the moves that led to the current state as [`Steps`].
+    pub fn to_steps(&self) -> Steps {
+        self.history()
+            .iter()
+            .map(|record| (record.src_no, record.dir))
+            .collect()
+    }
+
+    /// Goes to the state after the first `position` moves of the whole history,
+    /// including the moves that can be redone.
+    ///
+    /// Does nothing if `position` is beyond the history.
+    pub fn jump_to(&mut self, position: usize) {
+        if self.history.len() < position {
+            return;
+        }
+        self.ratios = self.initial.clone();
+        for record in &self.history[..position] {
+            record.replay(&mut self.ratios);
+        }
+        self.position = position;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answer::steps::Steps;
+    use crate::calculator::small_size::SmallSizeCalculator;
+    use crate::move_dir::MoveDir;
+    use crate::operation::Operation;
+    use crate::ratio::Ratio;
+    use crate::stage::history::{MoveKind, MoveRecord};
+    use crate::stage::Stage;
+
+    #[test]
+    fn record_moves() {
+        let mut stage = stage();
+        stage.move_cell(2, MoveDir::LeftUp).unwrap();
+        stage.move_cell(1, MoveDir::Down).unwrap();
+        assert_eq!(stage.history(), [
+            MoveRecord {
+                src_no: 2,
+                dist_no: 0,
+                dir: MoveDir::LeftUp,
+                kind: MoveKind::Combine,
+                operation: Operation::Add,
+                src_ratio: Ratio::from(3),
+                dist_ratio: Some(Ratio::from(1)),
+                result: Some(Ratio::from(4)),
+            },
+            MoveRecord {
+                src_no: 1,
+                dist_no: 2,
+                dir: MoveDir::Down,
+                kind: MoveKind::Swap,
+                operation: Operation::Swap,
+                src_ratio: Ratio::from(2),
+                dist_ratio: None,
+                result: None,
+            },
+        ]);
+        assert_eq!(stage.to_steps(), Steps::from_iter([(2, MoveDir::LeftUp), (1, MoveDir::Down)]));
+    }
+
+    #[test]
+    fn illegal_move_is_not_recorded() {
+        let mut stage = stage();
+        assert!(stage.move_cell(0, MoveDir::Left).is_err());
+        assert!(stage.history().is_empty());
+    }
+
+    #[test]
+    fn undo_keeps_redo_history() {
+        let mut stage = stage();
+        stage.move_cell(2, MoveDir::LeftUp).unwrap();
+        stage.move_cell(1, MoveDir::Down).unwrap();
+        stage.undo();
+        assert_eq!(stage.position(), 1);
+        assert_eq!(stage.redo_history().len(), 1);
+        assert_eq!(stage.redo_history()[0].src_no, 1);
+        // A new move discards the moves that could be redone.
+        stage.move_cell(3, MoveDir::LeftUp).unwrap();
+        assert!(stage.redo_history().is_empty());
+        assert_eq!(stage.history().len(), 2);
+    }
+
+    #[test]
+    fn jump_to() {
+        let mut stage = stage();
+        stage.move_cell(2, MoveDir::LeftUp).unwrap();
+        let after_first = *stage.movable_ratios();
+        stage.move_cell(1, MoveDir::LeftDown).unwrap();
+        stage.move_cell(3, MoveDir::Left).unwrap();
+        let last = *stage.movable_ratios();
+
+        stage.jump_to(1);
+        assert_eq!(stage.movable_ratios(), &after_first);
+        stage.jump_to(0);
+        assert_eq!(stage.movable_ratios(), self::stage().movable_ratios());
+        stage.jump_to(3);
+        assert_eq!(stage.movable_ratios(), &last);
+        stage.jump_to(4);
+        assert_eq!(stage.position(), 3);
+    }
+
+    fn stage() -> Stage<4, SmallSizeCalculator> {
+        Stage::from([
+            1, 2, 3, 4,
+        ])
+    }
+}
@@ -0,0 +1,112 @@
+//! What happened on a move, or why it couldn't be played.
+
+use std::fmt::{Display, Formatter};
+
+use crate::ratio::{ArithmeticError, Ratio};
+use crate::stage::history::{MoveKind, MoveRecord};
+
+/// The result of a move played by [`Stage::move_cell`](crate::stage::Stage::move_cell).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum MoveOutcome<Num = Ratio> {
+    /// The cells on `src_no` and `dist_no` were exchanged, and the one now on `dist_no` is marked as moved.
+    Swap {
+        src_no: usize,
+        dist_no: usize,
+    },
+    /// The cell on `src_no` was merged into `dist_no`, which now holds `result`.
+    Combine {
+        src_no: usize,
+        dist_no: usize,
+        result: Num,
+    },
+}
+
+impl<Num: Clone> MoveOutcome<Num> {
+    #[inline]
+    pub const fn kind(&self) -> MoveKind {
+        match self {
+            MoveOutcome::Swap { .. } => MoveKind::Swap,
+            MoveOutcome::Combine { .. } => MoveKind::Combine,
+        }
+    }
+
+    /// Returns the source and the destination of the move.
+    #[inline]
+    pub const fn cells(&self) -> (usize, usize) {
+        match *self {
+            MoveOutcome::Swap { src_no, dist_no } |
+            MoveOutcome::Combine { src_no, dist_no, .. } => (src_no, dist_no)
+        }
+    }
+
+    /// Returns the value left on the destination by a combine.
+    #[inline]
+    pub fn result(&self) -> Option<Num> {
+        match self {
+            MoveOutcome::Swap { .. } => None,
+            MoveOutcome::Combine { result, .. } => Some(result.clone()),
+        }
+    }
+}
+
+impl<Num: Clone> From<&MoveRecord<Num>> for MoveOutcome<Num> {
+    fn from(record: &MoveRecord<Num>) -> Self {
+        match &record.result {
+            Some(result) => MoveOutcome::Combine {
+                src_no: record.src_no,
+                dist_no: record.dist_no,
+                result: result.clone(),
+            },
+            None => MoveOutcome::Swap {
+                src_no: record.src_no,
+                dist_no: record.dist_no,
+            },
+        }
+    }
+}
+
+/// The reason a move can't be played.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum MoveError {
+    /// The source cell has already been moved.
+    AlreadyMoved,
+    /// The source or the destination is outside the board.
+    OffBoard,
+    /// There is no cell on the source.
+    EmptySource,
+    /// The operation would divide by zero.
+    DivisionByZero,
+    /// The result of the operation doesn't fit in the cell.
+    Overflow,
+    /// The operation isn't defined for the operands, such as a fractional exponent.
+    Undefined,
+    /// The calculator doesn't allow the move for another reason.
+    NotAllowed,
+}
+
+impl From<ArithmeticError> for MoveError {
+    fn from(error: ArithmeticError) -> Self {
+        match error {
+            ArithmeticError::Overflow => MoveError::Overflow,
+            ArithmeticError::DivisionByZero => MoveError::DivisionByZero,
+            ArithmeticError::Undefined => MoveError::Undefined,
+        }
+    }
+}
+
+impl Display for MoveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            MoveError::AlreadyMoved => "the cell has already been moved",
+            MoveError::OffBoard => "the move leaves the board",
+            MoveError::EmptySource => "there is no cell to move",
+            MoveError::DivisionByZero => "division by zero",
+            MoveError::Overflow => "the result is too large",
+            MoveError::Undefined => "the operation is undefined",
+            MoveError::NotAllowed => "the move is not allowed",
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for MoveError {}
@@ -0,0 +1,190 @@
+//! A copy of the whole state of a [`Stage`] that can be saved and restored.
+
+use std::fmt::{Display, Formatter};
+
+use crate::calculator::Calculator;
+use crate::movable_ratio::MovableRatio;
+use crate::number::PuzzleNumber;
+use crate::ratio::Ratio;
+use crate::rule_set::RuleSet;
+use crate::stage::history::MoveRecord;
+use crate::stage::{Stage, StageRatioArray};
+
+/// The initial cells, the rule set and the move history of a [`Stage`].
+///
+/// The cells are stored in a `Vec` rather than an array of `STAGE_SIZE`,
+/// so a snapshot can be serialized without knowing the board size.
+/// The calculator isn't stored; pass it again to [`Stage::from_snapshot`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StageSnapshot<Num = Ratio> {
+    pub initial: Vec<Option<MovableRatio<Num>>>,
+    pub rules: RuleSet,
+    /// Includes the moves that can be redone after `position`.
+    pub history: Vec<MoveRecord<Num>>,
+    /// The number of moves that led to the current state.
+    pub position: usize,
+}
+
+/// The error returned when a [`StageSnapshot`] can't be restored.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum SnapshotError {
+    /// The snapshot doesn't have as many cells as the stage.
+    Size {
+        expected: usize,
+        actual: usize,
+    },
+    /// The move at `index` of the history isn't allowed, or doesn't match its record.
+    IllegalMove {
+        index: usize,
+    },
+    /// The position is beyond the history.
+    Position {
+        position: usize,
+        len: usize,
+    },
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::Size { expected, actual } => write!(f, "the snapshot has {actual} cells, but the stage has {expected}"),
+            SnapshotError::IllegalMove { index } => write!(f, "move {index} of the history can't be played"),
+            SnapshotError::Position { position, len } => write!(f, "position {position} is beyond the history of {len} moves"),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+impl<const STAGE_SIZE: usize, Calc, Num> Stage<STAGE_SIZE, Calc, Num>
+    where Calc: Calculator + 'static,
+          Num: PuzzleNumber
+{
+    pub fn snapshot(&self) -> StageSnapshot<Num> {
+        StageSnapshot {
+            initial: self.initial.to_vec(),
+            rules: self.rules,
+            history: self.history.clone(),
+            position: self.position,
+        }
+    }
+
+    /// Restores a stage saved by [`Stage::snapshot`].
+    ///
+    /// The history is played again from the initial cells,
+    /// so a snapshot with a move that isn't allowed is rejected.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `STAGE_SIZE` differs from the number of cells of the calculator's topology.
+    pub fn from_snapshot(calculator: Calc, snapshot: &StageSnapshot<Num>) -> Result<Self, SnapshotError> {
+        let mut stage = Self::with_rules(calculator, snapshot.rules, std::array::from_fn(|_| Num::from_integer(0)));
+        stage.initial = to_array(&snapshot.initial)?;
+        stage.ratios = stage.initial.clone();
+        for (index, record) in snapshot.history.iter().enumerate() {
+            if STAGE_SIZE <= record.src_no {
+                return Err(SnapshotError::IllegalMove { index });
+            }
+            if stage.move_cell(record.src_no, record.dir).is_err() || stage.history.get(index) != Some(record) {
+                return Err(SnapshotError::IllegalMove { index });
+            }
+        }
+        if snapshot.history.len() < snapshot.position {
+            return Err(SnapshotError::Position {
+                position: snapshot.position,
+                len: snapshot.history.len(),
+            });
+        }
+        stage.jump_to(snapshot.position);
+        Ok(stage)
+    }
+}
+
+fn to_array<const STAGE_SIZE: usize, Num: Clone>(ratios: &[Option<MovableRatio<Num>>]) -> Result<StageRatioArray<STAGE_SIZE, Num>, SnapshotError> {
+    ratios.to_vec().try_into().map_err(|_| SnapshotError::Size {
+        expected: STAGE_SIZE,
+        actual: ratios.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::calculator::small_size::SmallSizeCalculator;
+    use crate::move_dir::MoveDir;
+    use crate::ratio::Ratio;
+    use crate::stage::snapshot::SnapshotError;
+    use crate::stage::Stage;
+
+    #[test]
+    fn restore_with_history() {
+        let played = played();
+        let mut restored = Stage::from_snapshot(SmallSizeCalculator, &played.snapshot()).unwrap();
+        assert_eq!(restored.movable_ratios(), played.movable_ratios());
+        restored.redo();
+        restored.undo();
+        restored.undo();
+        assert_eq!(restored.movable_ratios(), stage().movable_ratios());
+    }
+
+    #[test]
+    fn wrong_size() {
+        let mut snapshot = stage().snapshot();
+        snapshot.initial.pop();
+        assert_eq!(
+            Stage::<4, SmallSizeCalculator>::from_snapshot(SmallSizeCalculator, &snapshot).err(),
+            Some(SnapshotError::Size { expected: 4, actual: 3 })
+        );
+    }
+
+    #[test]
+    fn reject_tampered_history() {
+        let mut snapshot = played().snapshot();
+        snapshot.history[1].result = Some(Ratio::from(100));
+        assert_eq!(
+            Stage::<4, SmallSizeCalculator>::from_snapshot(SmallSizeCalculator, &snapshot).err(),
+            Some(SnapshotError::IllegalMove { index: 1 })
+        );
+
+        let mut snapshot = played().snapshot();
+        snapshot.position = 3;
+        assert_eq!(
+            Stage::<4, SmallSizeCalculator>::from_snapshot(SmallSizeCalculator, &snapshot).err(),
+            Some(SnapshotError::Position { position: 3, len: 2 })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json() {
+        let stage = played();
+        let json = serde_json::to_string(&stage.snapshot()).unwrap();
+        let snapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(stage.snapshot(), snapshot);
+        let restored = Stage::<4, SmallSizeCalculator>::from_snapshot(SmallSizeCalculator, &snapshot).unwrap();
+        assert_eq!(restored.snapshot(), stage.snapshot());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn ron() {
+        let stage = played();
+        let ron = ron::to_string(&stage.snapshot()).unwrap();
+        assert_eq!(ron::from_str::<crate::stage::snapshot::StageSnapshot>(&ron).unwrap(), stage.snapshot());
+    }
+
+    /// A stage with two moves, the last of which has been undone.
+    fn played() -> Stage<4, SmallSizeCalculator> {
+        let mut stage = stage();
+        stage.move_cell(2, MoveDir::LeftUp).unwrap();
+        stage.move_cell(1, MoveDir::LeftDown).unwrap();
+        stage.undo();
+        stage
+    }
+
+    fn stage() -> Stage<4, SmallSizeCalculator> {
+        Stage::from([
+            1, 2, 3, 4,
+        ])
+    }
+}
@@ -0,0 +1,68 @@
+//! The shape of a board.
+//!
+//! A [`Topology`] describes the board as a neighbour graph, `cell × direction → cell`,
+//! plus the 2D position of each cell.
+//! Everything that needs to know the board shape, such as [`Stage`](crate::stage::Stage),
+//! the calculators and the game's cell layout, reads it from here.
+
+pub mod diamond;
+pub mod grid;
+pub mod graph;
+pub mod hex;
+
+use crate::move_dir::MoveDir;
+
+/// The center position of a cell in cell units; `x` grows rightward and `y` grows upward.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct CellPos {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl CellPos {
+    #[inline]
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+}
+
+/// The outline of a cell, used when drawing the board.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum CellShape {
+    /// A square standing on one of its corners.
+    Diamond,
+    /// An axis-aligned square.
+    Square,
+    /// A hexagon with a corner pointing up.
+    Hexagon,
+}
+
+pub trait Topology {
+    /// Returns the number of cells on the board.
+    fn cell_count(&self) -> usize;
+
+    /// Returns the cell reached by moving from `cell_no` in `dir`,
+    /// or `None` if there is no cell in that direction.
+    fn neighbour(&self, cell_no: usize, dir: MoveDir) -> Option<usize>;
+
+    /// Returns the position of `cell_no`.
+    fn position(&self, cell_no: usize) -> CellPos;
+
+    /// Returns the directions cells on this board can move in.
+    ///
+    /// Directions not listed here never have a neighbour.
+    #[inline]
+    fn directions(&self) -> &[MoveDir] {
+        &MoveDir::ALL
+    }
+
+    #[inline]
+    fn cell_shape(&self) -> CellShape {
+        CellShape::Diamond
+    }
+
+    /// Returns the name of the board, written in the `Board` header of [`crate::notation`].
+    fn name(&self) -> String {
+        String::from("custom")
+    }
+}
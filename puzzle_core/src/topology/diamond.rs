@@ -0,0 +1,78 @@
+use crate::move_dir::MoveDir;
+use crate::topology::{CellPos, Topology};
+
+/// The four-cell diamond board.
+///
+/// ```text
+///     1
+///  0     3
+///     2
+/// ```
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct DiamondTopology;
+
+impl Topology for DiamondTopology {
+    fn name(&self) -> String {
+        String::from("diamond")
+    }
+
+    #[inline]
+    fn cell_count(&self) -> usize {
+        4
+    }
+
+    fn neighbour(&self, cell_no: usize, dir: MoveDir) -> Option<usize> {
+        match (cell_no, dir) {
+            (0, MoveDir::RightUp) => Some(1),
+            (0, MoveDir::Right) => Some(3),
+            (0, MoveDir::RightDown) => Some(2),
+            (1, MoveDir::LeftDown) => Some(0),
+            (1, MoveDir::Down) => Some(2),
+            (1, MoveDir::RightDown) => Some(3),
+            (2, MoveDir::LeftUp) => Some(0),
+            (2, MoveDir::Up) => Some(1),
+            (2, MoveDir::RightUp) => Some(3),
+            (3, MoveDir::LeftUp) => Some(1),
+            (3, MoveDir::Left) => Some(0),
+            (3, MoveDir::LeftDown) => Some(2),
+            _ => None
+        }
+    }
+
+    fn position(&self, cell_no: usize) -> CellPos {
+        match cell_no {
+            0 => CellPos::new(-1., 0.),
+            1 => CellPos::new(0., 1.),
+            2 => CellPos::new(0., -1.),
+            _ => CellPos::new(1., 0.),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::move_dir::MoveDir;
+    use crate::topology::diamond::DiamondTopology;
+    use crate::topology::Topology;
+
+    #[test]
+    fn neighbours_are_symmetric() {
+        let topology = DiamondTopology;
+        for cell_no in 0..topology.cell_count() {
+            for dir in MoveDir::ALL {
+                if let Some(dist_no) = topology.neighbour(cell_no, dir) {
+                    assert_eq!(topology.neighbour(dist_no, dir.reverse()), Some(cell_no));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn neighbours_match_positions() {
+        let topology = DiamondTopology;
+        let right_up = topology.position(1);
+        let left = topology.position(0);
+        assert!(left.x < right_up.x && left.y < right_up.y);
+        assert_eq!(topology.neighbour(0, MoveDir::RightUp), Some(1));
+    }
+}
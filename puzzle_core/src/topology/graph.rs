@@ -0,0 +1,89 @@
+use crate::move_dir::MoveDir;
+use crate::topology::{CellPos, Topology};
+
+/// A board of any shape, built by placing cells and connecting them.
+///
+/// ```
+/// use puzzle_core::move_dir::MoveDir;
+/// use puzzle_core::topology::{CellPos, Topology};
+/// use puzzle_core::topology::graph::GraphTopology;
+///
+/// // 0 - 1 - 2
+/// let line = GraphTopology::new([
+///     CellPos::new(-1., 0.),
+///     CellPos::new(0., 0.),
+///     CellPos::new(1., 0.),
+/// ])
+///     .connect(0, MoveDir::Right, 1)
+///     .connect(1, MoveDir::Right, 2);
+///
+/// assert_eq!(line.neighbour(2, MoveDir::Left), Some(1));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GraphTopology {
+    positions: Vec<CellPos>,
+    neighbours: Vec<[Option<usize>; 8]>,
+}
+
+impl GraphTopology {
+    /// Creates a board with a cell at each of `positions` and no connections.
+    pub fn new(positions: impl IntoIterator<Item=CellPos>) -> Self {
+        let positions = positions.into_iter().collect::<Vec<_>>();
+        Self {
+            neighbours: vec![[None; 8]; positions.len()],
+            positions,
+        }
+    }
+
+    /// Connects `from` to `to` in `dir`, and `to` back to `from` in the reverse direction.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if either cell is not on the board.
+    pub fn connect(mut self, from: usize, dir: MoveDir, to: usize) -> Self {
+        assert!(from < self.positions.len() && to < self.positions.len(), "cell out of the board");
+        self.neighbours[from][dir as usize] = Some(to);
+        self.neighbours[to][dir.reverse() as usize] = Some(from);
+        self
+    }
+}
+
+impl Topology for GraphTopology {
+    #[inline]
+    fn cell_count(&self) -> usize {
+        self.positions.len()
+    }
+
+    #[inline]
+    fn neighbour(&self, cell_no: usize, dir: MoveDir) -> Option<usize> {
+        self.neighbours.get(cell_no)?[dir as usize]
+    }
+
+    #[inline]
+    fn position(&self, cell_no: usize) -> CellPos {
+        self.positions[cell_no]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::move_dir::MoveDir;
+    use crate::topology::{CellPos, Topology};
+    use crate::topology::graph::GraphTopology;
+
+    #[test]
+    fn connect_both_ways() {
+        let graph = GraphTopology::new([CellPos::new(0., 0.), CellPos::new(1., 1.)])
+            .connect(0, MoveDir::RightUp, 1);
+        assert_eq!(graph.neighbour(0, MoveDir::RightUp), Some(1));
+        assert_eq!(graph.neighbour(1, MoveDir::LeftDown), Some(0));
+        assert_eq!(graph.neighbour(0, MoveDir::Right), None);
+        assert_eq!(graph.neighbour(2, MoveDir::Right), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn connect_out_of_board() {
+        let _ = GraphTopology::new([CellPos::new(0., 0.)]).connect(0, MoveDir::Right, 1);
+    }
+}
@@ -0,0 +1,92 @@
+use crate::move_dir::MoveDir;
+use crate::topology::{CellPos, CellShape, Topology};
+
+/// A rectangular board of `W` columns and `H` rows.
+///
+/// Cells are numbered row by row from the top left,
+/// and every cell is connected to its neighbours in all eight directions.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct GridTopology<const W: usize, const H: usize>;
+
+impl<const W: usize, const H: usize> GridTopology<W, H> {
+    /// Returns the `(x, y)` offset of `dir`, where `y` grows downward.
+    const fn offset(dir: MoveDir) -> (isize, isize) {
+        match dir {
+            MoveDir::LeftUp => (-1, -1),
+            MoveDir::Up => (0, -1),
+            MoveDir::RightUp => (1, -1),
+            MoveDir::Left => (-1, 0),
+            MoveDir::Right => (1, 0),
+            MoveDir::LeftDown => (-1, 1),
+            MoveDir::Down => (0, 1),
+            MoveDir::RightDown => (1, 1)
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> Topology for GridTopology<W, H> {
+    fn name(&self) -> String {
+        format!("grid {W}x{H}")
+    }
+
+    #[inline]
+    fn cell_count(&self) -> usize {
+        W * H
+    }
+
+    fn neighbour(&self, cell_no: usize, dir: MoveDir) -> Option<usize> {
+        if self.cell_count() <= cell_no {
+            return None;
+        }
+        let (dx, dy) = Self::offset(dir);
+        let x = (cell_no % W).checked_add_signed(dx)?;
+        let y = (cell_no / W).checked_add_signed(dy)?;
+        (x < W && y < H).then_some(y * W + x)
+    }
+
+    fn position(&self, cell_no: usize) -> CellPos {
+        let x = (cell_no % W) as f32 - (W - 1) as f32 / 2.;
+        let y = (H - 1) as f32 / 2. - (cell_no / W) as f32;
+        CellPos::new(x, y)
+    }
+
+    #[inline]
+    fn cell_shape(&self) -> CellShape {
+        CellShape::Square
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::move_dir::MoveDir;
+    use crate::topology::{CellPos, Topology};
+    use crate::topology::grid::GridTopology;
+
+    #[test]
+    fn no_wrap_around_rows() {
+        let grid = GridTopology::<3, 3>;
+        assert_eq!(grid.neighbour(2, MoveDir::Right), None);
+        assert_eq!(grid.neighbour(3, MoveDir::Left), None);
+        assert_eq!(grid.neighbour(3, MoveDir::LeftDown), None);
+        assert_eq!(grid.neighbour(5, MoveDir::RightUp), None);
+        assert_eq!(grid.neighbour(5, MoveDir::Left), Some(4));
+        assert_eq!(grid.neighbour(1, MoveDir::Down), Some(4));
+    }
+
+    #[test]
+    fn non_square() {
+        let grid = GridTopology::<2, 5>;
+        assert_eq!(grid.neighbour(1, MoveDir::Right), None);
+        assert_eq!(grid.neighbour(1, MoveDir::LeftDown), Some(2));
+        assert_eq!(grid.neighbour(8, MoveDir::Down), None);
+        assert_eq!(grid.neighbour(8, MoveDir::RightUp), Some(7));
+        assert_eq!(grid.neighbour(10, MoveDir::Up), None);
+    }
+
+    #[test]
+    fn positions_are_centered() {
+        let grid = GridTopology::<3, 2>;
+        assert_eq!(grid.position(0), CellPos::new(-1., 0.5));
+        assert_eq!(grid.position(4), CellPos::new(0., -0.5));
+    }
+}
@@ -0,0 +1,143 @@
+use crate::move_dir::MoveDir;
+use crate::topology::{CellPos, CellShape, Topology};
+
+/// The six directions of a hex board.
+///
+/// Each opposite pair keeps the meaning it has on the other boards:
+/// `Left`/`Right` swap, `LeftUp`/`RightDown` add and divide, `RightUp`/`LeftDown` subtract and multiply.
+pub const HEX_DIRECTIONS: [MoveDir; 6] = [
+    MoveDir::LeftUp,
+    MoveDir::RightUp,
+    MoveDir::Left,
+    MoveDir::Right,
+    MoveDir::LeftDown,
+    MoveDir::RightDown,
+];
+
+/// A board of pointy-top hexagons in `H` rows of `W` cells.
+///
+/// Cells are numbered row by row from the top left, and every odd row is shifted right by half a cell.
+///
+/// ```text
+///  0 1 2
+///   3 4 5
+///  6 7 8
+/// ```
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct HexTopology<const W: usize, const H: usize>;
+
+impl<const W: usize, const H: usize> HexTopology<W, H> {
+    /// Returns the `(x, y)` offset of `dir` from a cell in `row`, where `y` grows downward.
+    const fn offset(dir: MoveDir, row: usize) -> Option<(isize, isize)> {
+        let shift = (row % 2) as isize;
+        match dir {
+            MoveDir::LeftUp => Some((shift - 1, -1)),
+            MoveDir::RightUp => Some((shift, -1)),
+            MoveDir::Left => Some((-1, 0)),
+            MoveDir::Right => Some((1, 0)),
+            MoveDir::LeftDown => Some((shift - 1, 1)),
+            MoveDir::RightDown => Some((shift, 1)),
+            MoveDir::Up | MoveDir::Down => None
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> Topology for HexTopology<W, H> {
+    fn name(&self) -> String {
+        format!("hex {W}x{H}")
+    }
+
+    #[inline]
+    fn cell_count(&self) -> usize {
+        W * H
+    }
+
+    fn neighbour(&self, cell_no: usize, dir: MoveDir) -> Option<usize> {
+        if self.cell_count() <= cell_no {
+            return None;
+        }
+        let row = cell_no / W;
+        let (dx, dy) = Self::offset(dir, row)?;
+        let x = (cell_no % W).checked_add_signed(dx)?;
+        let y = row.checked_add_signed(dy)?;
+        (x < W && y < H).then_some(y * W + x)
+    }
+
+    fn position(&self, cell_no: usize) -> CellPos {
+        const ROW_HEIGHT: f32 = 0.866_025_4; // sqrt(3) / 2
+        let row = cell_no / W;
+        let shift = (row % 2) as f32 / 2.;
+        let x = (cell_no % W) as f32 + shift - ((W - 1) as f32 + if 1 < H { 0.5 } else { 0. }) / 2.;
+        let y = ((H - 1) as f32 / 2. - row as f32) * ROW_HEIGHT;
+        CellPos::new(x, y)
+    }
+
+    #[inline]
+    fn directions(&self) -> &[MoveDir] {
+        &HEX_DIRECTIONS
+    }
+
+    #[inline]
+    fn cell_shape(&self) -> CellShape {
+        CellShape::Hexagon
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::move_dir::MoveDir;
+    use crate::topology::hex::HexTopology;
+    use crate::topology::Topology;
+
+    type Hex3x3 = HexTopology<3, 3>;
+
+    #[test]
+    fn neighbours_are_symmetric() {
+        let hex = Hex3x3::default();
+        for cell_no in 0..hex.cell_count() {
+            for dir in MoveDir::ALL {
+                if let Some(dist_no) = hex.neighbour(cell_no, dir) {
+                    assert_eq!(hex.neighbour(dist_no, dir.reverse()), Some(cell_no));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn center_has_six_neighbours() {
+        let hex = Hex3x3::default();
+        let neighbours = MoveDir::ALL
+            .into_iter()
+            .filter_map(|dir| hex.neighbour(4, dir))
+            .collect::<Vec<_>>();
+        assert_eq!(neighbours, vec![1, 2, 3, 5, 7, 8]);
+    }
+
+    #[test]
+    fn even_row() {
+        let hex = Hex3x3::default();
+        assert_eq!(hex.neighbour(0, MoveDir::LeftDown), None);
+        assert_eq!(hex.neighbour(0, MoveDir::RightDown), Some(3));
+        assert_eq!(hex.neighbour(6, MoveDir::RightUp), Some(3));
+        assert_eq!(hex.neighbour(2, MoveDir::Right), None);
+    }
+
+    #[test]
+    fn no_vertical_moves() {
+        let hex = Hex3x3::default();
+        assert_eq!(hex.neighbour(4, MoveDir::Up), None);
+        assert_eq!(hex.neighbour(4, MoveDir::Down), None);
+        assert!(!hex.directions().contains(&MoveDir::Up));
+    }
+
+    #[test]
+    fn neighbours_are_equidistant() {
+        let hex = Hex3x3::default();
+        let center = hex.position(4);
+        for dir in hex.directions() {
+            let pos = hex.position(hex.neighbour(4, *dir).unwrap());
+            let distance = ((pos.x - center.x).powi(2) + (pos.y - center.y).powi(2)).sqrt();
+            assert!((distance - 1.).abs() < 1e-5, "{dir:?}: {distance}");
+        }
+    }
+}